@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// The result of solving one part of a puzzle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Unsolved,
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+            Self::Unsolved => "unsolved".fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_compare_across_types() {
+        assert_eq!(Answer::from(41_usize), Answer::from(41_i32));
+        assert_ne!(Answer::from(-1_i64), Answer::from(1_u64));
+    }
+
+    #[test]
+    fn display_pads() {
+        assert_eq!("42   ", format!("{:<5}", Answer::from(42_u32)));
+        assert_eq!("unsolved", Answer::Unsolved.to_string());
+        assert_eq!("abc", Answer::from("abc").to_string());
+    }
+}
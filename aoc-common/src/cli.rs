@@ -0,0 +1,134 @@
+//! Command line handling shared by the binaries.
+
+use crate::log;
+use std::env;
+use std::fmt;
+use std::iter::Skip;
+use std::process::ExitCode;
+use std::vec;
+
+/// Command line arguments taken one at a time, where a flag can also take
+/// the argument after it as its value.
+#[derive(Debug)]
+pub struct Args<I: Iterator<Item = String>> {
+    args: I,
+}
+
+impl Args<Skip<env::Args>> {
+    /// The arguments the program was started with, less its own name.
+    pub fn from_env() -> Self {
+        Self::new(env::args().skip(1))
+    }
+}
+
+impl Args<vec::IntoIter<String>> {
+    pub fn from_strs(args: &[&str]) -> Self {
+        Self::new(
+            args.iter()
+                .map(|arg| arg.to_string())
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+}
+
+impl<I: Iterator<Item = String>> Args<I> {
+    pub fn new(args: I) -> Self {
+        Self { args }
+    }
+
+    /// The value given to `flag`, which is the next argument.
+    pub fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.args
+            .next()
+            .ok_or_else(|| format!("`{flag}` expects a value"))
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Args<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.args.next()
+    }
+}
+
+/// How many levels louder a `-v`, `-vv`, `-vvv`, ... or `--verbose` flag
+/// asks the log to be, or `None` if `arg` isn't one of them.
+pub fn verbosity(arg: &str) -> Option<u8> {
+    if arg == "--verbose" {
+        return Some(1);
+    }
+    let vs = arg.strip_prefix('-')?;
+    (!vs.is_empty() && vs.bytes().all(|b| b == b'v')).then(|| vs.len().min(u8::MAX.into()) as u8)
+}
+
+/// Sets the log level from the environment, then raises it once for each
+/// level of `verbosity`.
+pub fn init_log(verbosity: u8) {
+    log::init_from_env();
+    for _ in 0..verbosity {
+        log::set_level(log::level().louder());
+    }
+}
+
+/// Reports a failed run on stderr and turns the outcome into an exit code.
+pub fn exit_code<E: fmt::Display>(result: Result<(), E>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the program's arguments with `parse` and hands the options to
+/// `run`. `parse` returns `None` when help was asked for, which prints
+/// `usage`; bad arguments print the problem followed by `usage`.
+pub fn run_main<O, E, P, R>(usage: &str, parse: P, run: R) -> ExitCode
+where
+    E: fmt::Display,
+    P: FnOnce(Args<Skip<env::Args>>) -> Result<Option<O>, String>,
+    R: FnOnce(&O) -> Result<(), E>,
+{
+    match parse(Args::from_env()) {
+        Ok(Some(options)) => exit_code(run(&options)),
+        Ok(None) => {
+            println!("{usage}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{usage}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn args_value() {
+        let mut args = Args::from_strs(&["--day", "6", "--part"]);
+        let flag = args.next().unwrap();
+        assert_eq!(Ok(String::from("6")), args.value(&flag));
+        let flag = args.next().unwrap();
+        assert_eq!(
+            Err(String::from("`--part` expects a value")),
+            args.value(&flag)
+        );
+        assert_eq!(None, args.next());
+    }
+
+    #[test]
+    fn verbosity_flags() {
+        assert_eq!(Some(1), verbosity("-v"));
+        assert_eq!(Some(1), verbosity("--verbose"));
+        assert_eq!(Some(3), verbosity("-vvv"));
+        assert_eq!(None, verbosity("-"));
+        assert_eq!(None, verbosity("-vx"));
+        assert_eq!(None, verbosity("--vv"));
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{ParseError, ParseErrorKind, Position};
+
+/// A forward-only reader over a byte buffer with the ability to save and
+/// restore its position.
+#[derive(Debug)]
+pub struct ByteCursor<T: AsRef<[u8]> = Box<[u8]>> {
+    data: T,
+    pub cursor: usize,
+}
+
+/// A saved cursor position that can be handed back to [`ByteCursor::rewind`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Checkpoint(usize);
+
+impl<T: AsRef<[u8]>> ByteCursor<T> {
+    pub fn new(data: T) -> Self {
+        Self { data, cursor: 0 }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data().is_empty()
+    }
+
+    #[inline]
+    pub fn eof(&self) -> bool {
+        self.cursor >= self.len()
+    }
+
+    pub fn peek(&self) -> Option<&u8> {
+        self.data().get(self.cursor)
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        let byte = self.peek().copied();
+        if byte.is_some() {
+            self.cursor += 1;
+        }
+        byte
+    }
+
+    /// Advances past every byte matching `predicate`, returning how many were
+    /// skipped.
+    pub fn skip_while<F: FnMut(u8) -> bool>(&mut self, mut predicate: F) -> usize {
+        let start = self.cursor;
+        while let Some(b) = self.peek() {
+            if predicate(*b) {
+                self.cursor += 1;
+            } else {
+                break;
+            }
+        }
+        self.cursor - start
+    }
+
+    /// Reads a run of ASCII digits. Returns `None` and leaves the cursor
+    /// untouched if there is no digit under the cursor or the value does not
+    /// fit in a `u64`.
+    pub fn read_unsigned(&mut self) -> Option<u64> {
+        let checkpoint = self.checkpoint();
+        let mut value: Option<u64> = None;
+        while let Some(b) = self.peek() {
+            if !b.is_ascii_digit() {
+                break;
+            }
+            let digit = u64::from(*b - b'0');
+            value = value
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit));
+            if value.is_none() {
+                self.rewind(checkpoint);
+                return None;
+            }
+            self.cursor += 1;
+        }
+        value
+    }
+
+    /// Reads an optionally signed run of ASCII digits. Returns `None` and
+    /// leaves the cursor untouched if no number is present or it does not fit
+    /// in an `i64`.
+    pub fn read_signed(&mut self) -> Option<i64> {
+        let checkpoint = self.checkpoint();
+        let negative = match self.peek() {
+            Some(b'-') => {
+                self.cursor += 1;
+                true
+            }
+            Some(b'+') => {
+                self.cursor += 1;
+                false
+            }
+            _ => false,
+        };
+
+        let value = self.read_unsigned().and_then(|v| {
+            if negative {
+                0_i64.checked_sub_unsigned(v)
+            } else {
+                i64::try_from(v).ok()
+            }
+        });
+        if value.is_none() {
+            self.rewind(checkpoint);
+        }
+        value
+    }
+
+    /// Consumes `literal` if the upcoming bytes match it exactly, otherwise
+    /// leaves the cursor where it was.
+    pub fn expect_literal(&mut self, literal: &[u8]) -> bool {
+        if self.data()[self.cursor.min(self.len())..].starts_with(literal) {
+            self.cursor += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The line and column of the byte under the cursor.
+    pub fn position(&self) -> Position {
+        Position::of_offset(self.data(), self.cursor)
+    }
+
+    /// Builds a [`ParseError`] located at the cursor.
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, self.position())
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.cursor)
+    }
+
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.cursor = checkpoint.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_data_is_eof() {
+        let mut cursor = ByteCursor::new(b"");
+        assert!(cursor.eof());
+        assert_eq!(None, cursor.peek());
+        assert_eq!(None, cursor.read_byte());
+    }
+
+    #[test]
+    fn read_byte_advances() {
+        let mut cursor = ByteCursor::new(b"ab");
+        assert_eq!(Some(b'a'), cursor.read_byte());
+        assert_eq!(Some(&b'b'), cursor.peek());
+        assert_eq!(Some(b'b'), cursor.read_byte());
+        assert_eq!(None, cursor.read_byte());
+        assert_eq!(2, cursor.cursor);
+    }
+
+    #[test]
+    fn skip_while_counts() {
+        let mut cursor = ByteCursor::new(b"   7");
+        assert_eq!(3, cursor.skip_while(|b| b == b' '));
+        assert_eq!(0, cursor.skip_while(|b| b == b' '));
+        assert_eq!(Some(&b'7'), cursor.peek());
+    }
+
+    #[test]
+    fn read_unsigned_values() {
+        let mut cursor = ByteCursor::new(b"042,x");
+        assert_eq!(Some(42), cursor.read_unsigned());
+        assert_eq!(3, cursor.cursor);
+        assert_eq!(None, cursor.read_unsigned());
+        assert_eq!(3, cursor.cursor);
+    }
+
+    #[test]
+    fn read_unsigned_overflow_rewinds() {
+        let mut cursor = ByteCursor::new(b"99999999999999999999");
+        assert_eq!(None, cursor.read_unsigned());
+        assert_eq!(0, cursor.cursor);
+    }
+
+    #[test]
+    fn read_signed_values() {
+        let mut cursor = ByteCursor::new(b"-12 +3 4 -");
+        assert_eq!(Some(-12), cursor.read_signed());
+        cursor.skip_while(|b| b == b' ');
+        assert_eq!(Some(3), cursor.read_signed());
+        cursor.skip_while(|b| b == b' ');
+        assert_eq!(Some(4), cursor.read_signed());
+        cursor.skip_while(|b| b == b' ');
+        assert_eq!(None, cursor.read_signed());
+        assert_eq!(9, cursor.cursor);
+    }
+
+    #[test]
+    fn read_signed_min() {
+        let mut cursor = ByteCursor::new(b"-9223372036854775808");
+        assert_eq!(Some(i64::MIN), cursor.read_signed());
+    }
+
+    #[test]
+    fn expect_literal_matches() {
+        let mut cursor = ByteCursor::new(b"mul(1,2)");
+        assert!(!cursor.expect_literal(b"mux"));
+        assert_eq!(0, cursor.cursor);
+        assert!(cursor.expect_literal(b"mul("));
+        assert_eq!(4, cursor.cursor);
+        assert!(!cursor.expect_literal(b"1,2)x"));
+    }
+
+    #[test]
+    fn error_position() {
+        let mut cursor = ByteCursor::new(b"1 2\n3 x");
+        cursor.cursor = 6;
+        let err = cursor.error(ParseErrorKind::UnexpectedByte(b'x'));
+        assert_eq!(Some(Position::new(2, 3)), err.position);
+    }
+
+    #[test]
+    fn checkpoint_rewind() {
+        let mut cursor = ByteCursor::new(b"abc");
+        let checkpoint = cursor.checkpoint();
+        cursor.read_byte();
+        cursor.read_byte();
+        cursor.rewind(checkpoint);
+        assert_eq!(Some(b'a'), cursor.read_byte());
+    }
+}
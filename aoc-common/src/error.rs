@@ -0,0 +1,191 @@
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::Path;
+
+/// A 1-based line and column within an input file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// Works out the line and column of byte `offset` within `data`.
+    pub fn of_offset(data: &[u8], offset: usize) -> Self {
+        let before = &data[..offset.min(data.len())];
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        Self::new(line, offset - line_start + 1)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    MissingFile {
+        path: String,
+        reason: String,
+    },
+    Io(String),
+    MalformedNumber(String),
+    UnexpectedByte(u8),
+    UnexpectedEnd,
+    RaggedRow {
+        expected: usize,
+        found: usize,
+    },
+    /// Something the input must contain exactly one of wasn't there.
+    Missing(String),
+    /// A second one of something the input must contain exactly one of.
+    Duplicate(String),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFile { path, reason } => write!(f, "unable to read `{path}`: {reason}"),
+            Self::Io(reason) => write!(f, "read failed: {reason}"),
+            Self::MalformedNumber(token) => write!(f, "malformed number `{token}`"),
+            Self::UnexpectedByte(b) if b.is_ascii_graphic() => {
+                write!(f, "unexpected byte `{}`", *b as char)
+            }
+            Self::UnexpectedByte(b) => write!(f, "unexpected byte {b:#04x}"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of line"),
+            Self::RaggedRow { expected, found } => {
+                write!(f, "row is {found} wide, expected {expected}")
+            }
+            Self::Missing(what) => write!(f, "no {what} found"),
+            Self::Duplicate(what) => write!(f, "more than one {what}"),
+        }
+    }
+}
+
+/// Raised when a day's input cannot be read or turned into its parsed form.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub position: Option<Position>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, position: Position) -> Self {
+        Self {
+            kind,
+            position: Some(position),
+        }
+    }
+
+    pub fn missing_file<P: AsRef<Path>>(path: P, err: &io::Error) -> Self {
+        Self {
+            kind: ParseErrorKind::MissingFile {
+                path: path.as_ref().display().to_string(),
+                reason: err.to_string(),
+            },
+            position: None,
+        }
+    }
+
+    /// A read that failed part way through an input stream.
+    pub fn io(err: &io::Error) -> Self {
+        Self {
+            kind: ParseErrorKind::Io(err.to_string()),
+            position: None,
+        }
+    }
+
+    /// The input has no `what` anywhere in it.
+    pub fn missing(what: &str) -> Self {
+        Self {
+            kind: ParseErrorKind::Missing(String::from(what)),
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{position}: {}", self.kind),
+            None => self.kind.fmt(f),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Reads a whole input file, reporting a missing or unreadable file as a
+/// [`ParseError`].
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    fs::read(&path).map_err(|err| ParseError::missing_file(path, &err))
+}
+
+/// Opens an input file for reading a piece at a time, with the same error as
+/// [`read_input`] if it is missing.
+pub fn open_input<P: AsRef<Path>>(path: P) -> Result<BufReader<File>> {
+    File::open(&path)
+        .map(BufReader::new)
+        .map_err(|err| ParseError::missing_file(path, &err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_offset() {
+        let data = b"ab\ncd\n\nef";
+        assert_eq!(Position::new(1, 1), Position::of_offset(data, 0));
+        assert_eq!(Position::new(1, 3), Position::of_offset(data, 2));
+        assert_eq!(Position::new(2, 2), Position::of_offset(data, 4));
+        assert_eq!(Position::new(4, 1), Position::of_offset(data, 7));
+        assert_eq!(Position::new(4, 3), Position::of_offset(data, 9));
+    }
+
+    #[test]
+    fn display_with_position() {
+        let err = ParseError::new(
+            ParseErrorKind::MalformedNumber(String::from("1x")),
+            Position::new(3, 5),
+        );
+        assert_eq!("line 3, column 5: malformed number `1x`", err.to_string());
+    }
+
+    #[test]
+    fn display_missing_and_duplicate() {
+        assert_eq!("no guard found", ParseError::missing("guard").to_string());
+        let err = ParseError::new(
+            ParseErrorKind::Duplicate(String::from("guard")),
+            Position::new(2, 7),
+        );
+        assert_eq!("line 2, column 7: more than one guard", err.to_string());
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = read_input("./does/not/exist.txt").unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::MissingFile { .. }));
+        assert_eq!(None, err.position);
+        assert!(err
+            .to_string()
+            .starts_with("unable to read `./does/not/exist.txt`"));
+
+        let err = open_input("./does/not/exist.txt").unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::MissingFile { .. }));
+    }
+}
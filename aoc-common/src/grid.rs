@@ -0,0 +1,306 @@
+use crate::{ParseError, ParseErrorKind, Position, Result};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Cords = (usize, usize);
+
+/// Offsets to the orthogonal neighbours, clockwise from up.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the orthogonal and diagonal neighbours, clockwise from up.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row-major without any line terminators.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    cells: Box<[T]>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid<u8> {
+    /// Builds a grid from newline separated rows, which may end in `\r\n`.
+    /// The width is taken from the first row; blank lines at the end are
+    /// ignored.
+    pub fn parse(raw_data: &[u8]) -> Result<Self> {
+        let mut rows: Vec<&[u8]> = raw_data
+            .split(|&b| b == b'\n')
+            .map(|row| row.strip_suffix(b"\r").unwrap_or(row))
+            .collect();
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut cells = Vec::with_capacity(raw_data.len());
+        let mut height = 0;
+        for row in rows {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    ParseErrorKind::RaggedRow {
+                        expected: width,
+                        found: row.len(),
+                    },
+                    Position::new(height + 1, width.min(row.len()) + 1),
+                ));
+            }
+            cells.extend_from_slice(row);
+            height += 1;
+        }
+
+        Ok(Self::new(width, height, cells))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "grid size mismatch");
+        Self {
+            cells: cells.into_boxed_slice(),
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn<F: FnMut(Cords) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn index(&self, (x, y): Cords) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn cords(&self, index: usize) -> Cords {
+        (index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index((x, y)).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index((x, y)).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Moves `pos` by `(dx, dy)`, returning `None` if that leaves the grid.
+    pub fn offset(&self, (x, y): Cords, (dx, dy): (isize, isize)) -> Option<Cords> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        self.index((x, y)).map(|_| (x, y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Cords, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.cords(idx), cell))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbors_4(&self, pos: Cords) -> impl Iterator<Item = Cords> + '_ {
+        NEIGHBORS_4
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that lie inside the
+    /// grid.
+    pub fn neighbors_8(&self, pos: Cords) -> impl Iterator<Item = Cords> + '_ {
+        NEIGHBORS_8
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    pub fn find<F: FnMut(&T) -> bool>(&self, predicate: F) -> Option<Cords> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|idx| self.cords(idx))
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn position_of(&self, value: &T) -> Option<Cords> {
+        self.find(|cell| cell == value)
+    }
+}
+
+impl<T> Index<Cords> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Cords) -> &Self::Output {
+        self.get(x, y).expect("INDEX OUT OF BOUNDS")
+    }
+}
+
+impl<T> IndexMut<Cords> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Cords) -> &mut Self::Output {
+        self.get_mut(x, y).expect("INDEX OUT OF BOUNDS")
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(&String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_grid() -> Grid<u8> {
+        Grid::parse(b"abc\ndef\nghi\njkl\n").unwrap()
+    }
+
+    #[test]
+    fn parse_dimensions() {
+        let grid = example_grid();
+        assert_eq!(3, grid.width());
+        assert_eq!(4, grid.height());
+
+        let crlf = Grid::parse(b"ab.\r\n.#.\r\n").unwrap();
+        assert_eq!((3, 2), (crlf.width(), crlf.height()));
+        assert_eq!(Some(&b'.'), crlf.get(2, 0));
+        assert_eq!(Some(&b'.'), crlf.get(2, 1));
+
+        for trailing in [
+            &b"..^\n...\n\n"[..],
+            b"..^\r\n...\r\n\r\n\r\n",
+            b"..^\n...\n\r",
+        ] {
+            let grid = Grid::parse(trailing).unwrap();
+            assert_eq!((3, 2), (grid.width(), grid.height()));
+        }
+        assert_eq!(0, Grid::parse(b"\n\n").unwrap().height());
+    }
+
+    #[test]
+    fn parse_ragged() {
+        let err = Grid::parse(b"abc\nde\nfgh").unwrap_err();
+        assert_eq!(
+            ParseErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            },
+            err.kind
+        );
+        assert_eq!(Some(Position::new(2, 3)), err.position);
+    }
+
+    #[test]
+    fn get_checked() {
+        let grid = example_grid();
+        assert_eq!(Some(&b'a'), grid.get(0, 0));
+        assert_eq!(Some(&b'l'), grid.get(2, 3));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(None, grid.get(0, 4));
+    }
+
+    #[test]
+    fn get_mut_updates() {
+        let mut grid = example_grid();
+        *grid.get_mut(1, 1).unwrap() = b'X';
+        assert_eq!(b'X', grid[(1, 1)]);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = example_grid();
+        assert_eq!(b"def", grid.row(1));
+        assert_eq!(4, grid.rows().count());
+        assert_eq!(
+            vec![b'b', b'e', b'h', b'k'],
+            grid.column(1).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.columns().count());
+    }
+
+    #[test]
+    fn neighbors_at_corner() {
+        let grid = example_grid();
+        let four: Vec<Cords> = grid.neighbors_4((0, 0)).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], four);
+        let eight: Vec<Cords> = grid.neighbors_8((0, 0)).collect();
+        assert_eq!(vec![(1, 0), (1, 1), (0, 1)], eight);
+        assert_eq!(8, grid.neighbors_8((1, 1)).count());
+    }
+
+    #[test]
+    fn find_position() {
+        let grid = example_grid();
+        assert_eq!(Some((1, 2)), grid.position_of(&b'h'));
+        assert_eq!(None, grid.position_of(&b'z'));
+        assert_eq!(Some((0, 1)), grid.find(|b| *b > b'c'));
+    }
+
+    #[test]
+    fn from_fn_and_map() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + y * 10);
+        assert_eq!(Some(&12), grid.get(2, 1));
+        let doubled = grid.map(|v| v * 2);
+        assert_eq!(Some(&24), doubled.get(2, 1));
+        assert_eq!(3, doubled.width());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let grid = example_grid();
+        assert_eq!("abc\ndef\nghi\njkl", grid.to_string());
+    }
+}
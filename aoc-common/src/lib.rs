@@ -0,0 +1,15 @@
+mod answer;
+pub mod cli;
+mod cursor;
+mod error;
+mod grid;
+pub mod log;
+mod solution;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use answer::Answer;
+pub use cursor::{ByteCursor, Checkpoint};
+pub use error::{open_input, read_input, ParseError, ParseErrorKind, Position, Result};
+pub use grid::{Cords, Grid, NEIGHBORS_4, NEIGHBORS_8};
+pub use solution::Solution;
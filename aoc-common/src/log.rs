@@ -0,0 +1,157 @@
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable [`init_from_env`] reads the level from.
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// How much diagnostic output to write to stderr, from least to most.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 6] = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// The next more verbose level, staying at `Trace` once reached.
+    pub fn louder(self) -> Self {
+        Self::ALL[(self as usize + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "OFF",
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown log level `{s}`"))
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// Whether messages at `level` are currently written.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+/// Sets the level from [`LOG_ENV`], leaving it alone when the variable is
+/// unset or not a level name.
+pub fn init_from_env() {
+    if let Some(level) = env::var(LOG_ENV).ok().and_then(|s| s.parse().ok()) {
+        set_level(level);
+    }
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, target: &str, args: fmt::Arguments<'_>) {
+    eprintln!("[{level:<5} {target}] {args}");
+}
+
+/// Writes a message to stderr when `level` is enabled. The arguments are
+/// only formatted if it is.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_from_str() {
+        assert_eq!(Ok(Level::Debug), "debug".parse());
+        assert_eq!(Ok(Level::Trace), " TRACE ".parse());
+        assert_eq!(Ok(Level::Off), "off".parse());
+        assert!("loud".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn level_louder() {
+        assert_eq!(Level::Info, Level::Warn.louder());
+        assert_eq!(Level::Trace, Level::Debug.louder());
+        assert_eq!(Level::Trace, Level::Trace.louder());
+    }
+
+    #[test]
+    fn level_enabled() {
+        let previous = level();
+        set_level(Level::Info);
+        assert!(enabled(Level::Warn));
+        assert!(enabled(Level::Info));
+        assert!(!enabled(Level::Debug));
+        set_level(Level::Off);
+        assert!(!enabled(Level::Error));
+        assert!(!enabled(Level::Off));
+        set_level(previous);
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{Answer, Result};
+
+/// A single day's puzzle. The input is parsed once and both parts are
+/// answered from the parsed form.
+pub trait Solution {
+    type Parsed;
+
+    const DAY: u8;
+    /// Where the day's input lives when no other path is given.
+    const INPUT: &'static str;
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed>;
+    fn part_one(parsed: &Self::Parsed) -> Answer;
+    fn part_two(parsed: &Self::Parsed) -> Answer;
+}
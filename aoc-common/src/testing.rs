@@ -0,0 +1,71 @@
+//! Helpers shared by the days' tests and benchmarks, behind the `testing`
+//! feature.
+
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// A small seeded pseudo-random generator, so generated inputs come out the
+/// same on every run and failures can be reproduced.
+#[derive(Clone, Debug)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn advance(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.state
+    }
+
+    /// The high 32 bits of the next state.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.advance() >> 32) as u32
+    }
+
+    /// A number below `modulus`, which must not be zero.
+    pub fn below(&mut self, modulus: u64) -> u64 {
+        (self.advance() >> 33) % modulus
+    }
+}
+
+/// Runs `f` once, returning what it gave back and how long it took.
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let value = black_box(f());
+    (value, start.elapsed())
+}
+
+/// The input size a benchmark was asked for: its first argument that isn't
+/// one of the `--` flags cargo passes along, or `default` if there is none.
+pub fn size_arg(default: usize) -> usize {
+    env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .map_or(default, |arg| {
+            arg.parse()
+                .unwrap_or_else(|_| panic!("`{arg}` is not a whole number"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_rng_repeats() {
+        let mut first = SeededRng::new(7);
+        let mut second = SeededRng::new(7);
+        let first: Vec<u64> = (0..20).map(|_| first.below(10)).collect();
+        assert_eq!(first, (0..20).map(|_| second.below(10)).collect::<Vec<_>>());
+        assert!(first.iter().all(|value| *value < 10));
+        assert_ne!(SeededRng::new(1).next_u32(), SeededRng::new(2).next_u32());
+    }
+}
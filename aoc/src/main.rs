@@ -0,0 +1,253 @@
+use aoc_common::cli::{self, Args};
+use aoc_common::{read_input, Answer, ParseError, Solution};
+use day_five::DayFive;
+use day_four::DayFour;
+use day_one::DayOne;
+use day_six::DaySix;
+use day_three::DayThree;
+use day_two::DayTwo;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage: aoc run [-v]... [--day <N>] [--part <1|2>] [--input <PATH>]
+
+Runs every solved day when --day is omitted. --input overrides the day's
+default input file and requires --day.
+
+Diagnostics go to stderr. The level starts from AOC_LOG (off, error, warn,
+info, debug or trace; warn when unset) and each -v raises it by one.";
+
+fn main() -> ExitCode {
+    cli::run_main(USAGE, parse_args, run)
+}
+
+struct Timed<T> {
+    value: T,
+    elapsed: Duration,
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> Timed<T> {
+    let start = Instant::now();
+    let value = f();
+    Timed {
+        value,
+        elapsed: start.elapsed(),
+    }
+}
+
+struct DayReport {
+    parse: Duration,
+    parts: Vec<(u8, Timed<Answer>)>,
+}
+
+type Runner = fn(&[u8], Option<u8>) -> Result<DayReport, ParseError>;
+
+/// Parses `input` once and answers the requested parts of `S`.
+fn solve<S: Solution>(input: &[u8], part: Option<u8>) -> Result<DayReport, ParseError> {
+    let parsed = timed(|| S::parse(input));
+    let data = parsed.value?;
+    let parts: [fn(&S::Parsed) -> Answer; 2] = [S::part_one, S::part_two];
+    let parts = (1..)
+        .zip(parts)
+        .filter(|(number, _)| part.is_none_or(|p| p == *number))
+        .map(|(number, part)| (number, timed(|| part(&data))))
+        .collect();
+    Ok(DayReport {
+        parse: parsed.elapsed,
+        parts,
+    })
+}
+
+struct Day {
+    number: u8,
+    input: &'static str,
+    runner: Runner,
+}
+
+impl Day {
+    const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            input: S::INPUT,
+            runner: solve::<S>,
+        }
+    }
+}
+
+const DAYS: [Day; 6] = [
+    Day::of::<DayOne>(),
+    Day::of::<DayTwo>(),
+    Day::of::<DayThree>(),
+    Day::of::<DayFour>(),
+    Day::of::<DayFive>(),
+    Day::of::<DaySix>(),
+];
+
+#[derive(Debug, Default, PartialEq)]
+struct RunArgs {
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    verbosity: u8,
+}
+
+/// Returns `None` when help was asked for.
+fn parse_args<I: Iterator<Item = String>>(mut args: Args<I>) -> Result<Option<RunArgs>, String> {
+    match args.next().as_deref() {
+        Some("run") => {}
+        Some("-h" | "--help" | "help") => return Ok(None),
+        Some(other) => return Err(format!("unknown command `{other}`")),
+        None => return Err(String::from("missing command")),
+    }
+
+    let mut run_args = RunArgs::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => run_args.day = Some(parse_number(&args.value(&arg)?, 1..=25)?),
+            "--part" => run_args.part = Some(parse_number(&args.value(&arg)?, 1..=2)?),
+            "--input" => run_args.input = Some(args.value(&arg)?),
+            "-h" | "--help" => return Ok(None),
+            _ => match cli::verbosity(&arg) {
+                Some(louder) => run_args.verbosity = run_args.verbosity.saturating_add(louder),
+                None => return Err(format!("unknown argument `{arg}`")),
+            },
+        }
+    }
+
+    if run_args.input.is_some() && run_args.day.is_none() {
+        return Err(String::from("`--input` requires `--day`"));
+    }
+    Ok(Some(run_args))
+}
+
+fn parse_number(value: &str, range: std::ops::RangeInclusive<u8>) -> Result<u8, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| range.contains(n))
+        .ok_or_else(|| {
+            format!(
+                "`{value}` must be between {} and {}",
+                range.start(),
+                range.end()
+            )
+        })
+}
+
+/// Runs the selected days, reporting each one that can't be run as it goes.
+fn run(args: &RunArgs) -> Result<(), String> {
+    cli::init_log(args.verbosity);
+    let days: Vec<&Day> = DAYS
+        .iter()
+        .filter(|day| args.day.is_none_or(|n| n == day.number))
+        .collect();
+    if days.is_empty() {
+        return Err(format!("day {} has no solution", args.day.unwrap_or(0)));
+    }
+
+    let selected = days.len();
+    let mut failed = 0;
+    for day in days {
+        let path = args.input.as_deref().unwrap_or(day.input);
+        let input = match read_input(path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("error: day {}: {e}", day.number);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let report = match (day.runner)(&input, args.part) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("error: day {}: {e}", day.number);
+                failed += 1;
+                continue;
+            }
+        };
+
+        println!(
+            "Day {:>2} parse:  {:<20} ({:.2?})",
+            day.number, "", report.parse
+        );
+        for (part_number, answer) in report.parts {
+            println!(
+                "Day {:>2} part {part_number}: {:<20} ({:.2?})",
+                day.number, answer.value, answer.elapsed
+            );
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{failed} of {selected} days could not be run")),
+    }
+}
+
+#[cfg(test)]
+mod aoc {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Option<RunArgs>, String> {
+        parse_args(Args::from_strs(args))
+    }
+
+    #[test]
+    fn parse_run_all() {
+        assert_eq!(Ok(Some(RunArgs::default())), args(&["run"]));
+    }
+
+    #[test]
+    fn parse_run_single_part() {
+        assert_eq!(
+            Ok(Some(RunArgs {
+                day: Some(6),
+                part: Some(2),
+                input: Some(String::from("map.txt")),
+                verbosity: 0,
+            })),
+            args(&["run", "--day", "6", "--part", "2", "--input", "map.txt"])
+        );
+    }
+
+    #[test]
+    fn parse_verbosity() {
+        assert_eq!(
+            Ok(Some(RunArgs {
+                verbosity: 3,
+                ..RunArgs::default()
+            })),
+            args(&["run", "-v", "--verbose", "-v"])
+        );
+        assert_eq!(
+            Ok(Some(RunArgs {
+                verbosity: 2,
+                ..RunArgs::default()
+            })),
+            args(&["run", "-vv"])
+        );
+        assert_eq!(
+            Ok(Some(RunArgs {
+                verbosity: 4,
+                ..RunArgs::default()
+            })),
+            args(&["run", "-vvv", "-v"])
+        );
+        assert!(args(&["run", "-vx"]).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_bad_part() {
+        assert!(args(&["run", "--part", "3"]).is_err());
+        assert!(args(&["run", "--day"]).is_err());
+        assert!(args(&["run", "--input", "map.txt"]).is_err());
+        assert!(args(&["walk"]).is_err());
+    }
+
+    #[test]
+    fn days_are_unique() {
+        for (idx, day) in DAYS.iter().enumerate() {
+            assert!(DAYS[idx + 1..].iter().all(|d| d.number != day.number));
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use aoc_common::{Answer, ByteCursor, ParseError, ParseErrorKind, Result, Solution};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+pub struct DayFive;
+
+impl Solution for DayFive {
+    type Parsed = PrintQueue;
+
+    const DAY: u8 = 5;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/data.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        PrintQueue::parse_data(input.to_vec())
+    }
+
+    fn part_one(queue: &Self::Parsed) -> Answer {
+        queue
+            .updates
+            .iter()
+            .filter(|update| queue.is_ordered(update))
+            .map(|update| middle_page(update))
+            .sum::<u32>()
+            .into()
+    }
+
+    fn part_two(queue: &Self::Parsed) -> Answer {
+        queue
+            .updates
+            .iter()
+            .filter(|update| !queue.is_ordered(update))
+            .map(|update| middle_page(&queue.reorder(update)))
+            .sum::<u32>()
+            .into()
+    }
+}
+
+/// The page ordering rules followed by the updates to check against them.
+#[derive(Debug)]
+pub struct PrintQueue {
+    /// Each `(before, after)` pair means `before` must be printed ahead of
+    /// `after` whenever both are in the same update.
+    pub rules: HashSet<(u32, u32)>,
+    pub updates: Vec<Vec<u32>>,
+}
+
+impl PrintQueue {
+    pub fn parse_data(raw_data: Vec<u8>) -> Result<Self> {
+        let mut data = ByteCursor::new(raw_data.into_boxed_slice());
+        let mut rules = HashSet::new();
+        let mut updates = Vec::new();
+
+        while !data.eof() && !line_ending(&mut data) {
+            let before = read_page(&mut data)?;
+            expect_byte(&mut data, b'|')?;
+            let after = read_page(&mut data)?;
+            end_line(&mut data)?;
+            rules.insert((before, after));
+        }
+
+        loop {
+            // Blank lines after the rules, such as a trailing one, hold no
+            // update.
+            while line_ending(&mut data) {}
+            if data.eof() {
+                break;
+            }
+            let mut update = vec![read_page(&mut data)?];
+            while data.expect_literal(b",") {
+                update.push(read_page(&mut data)?);
+            }
+            end_line(&mut data)?;
+            updates.push(update);
+        }
+
+        Ok(Self { rules, updates })
+    }
+
+    pub fn is_ordered(&self, update: &[u32]) -> bool {
+        update.iter().enumerate().all(|(idx, before)| {
+            update[idx + 1..]
+                .iter()
+                .all(|after| !self.rules.contains(&(*after, *before)))
+        })
+    }
+
+    /// Returns a copy of `update` sorted so that it satisfies every rule.
+    ///
+    /// Only some pairs of pages have a rule between them, so this is a
+    /// topological sort over the rules between the update's own pages rather
+    /// than a comparison sort. Pages the rules leave free keep their original
+    /// order, and any caught in a cycle of rules go last in that order too.
+    pub fn reorder(&self, update: &[u32]) -> Vec<u32> {
+        let mut followers: Vec<Vec<usize>> = vec![Vec::new(); update.len()];
+        let mut blockers = vec![0usize; update.len()];
+        for (before, page) in update.iter().enumerate() {
+            for (after, other) in update.iter().enumerate() {
+                if self.rules.contains(&(*page, *other)) {
+                    followers[before].push(after);
+                    blockers[after] += 1;
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..update.len())
+            .filter(|idx| blockers[*idx] == 0)
+            .map(Reverse)
+            .collect();
+        let mut placed = vec![false; update.len()];
+        let mut reordered = Vec::with_capacity(update.len());
+        while let Some(Reverse(idx)) = ready.pop() {
+            placed[idx] = true;
+            reordered.push(update[idx]);
+            for &after in &followers[idx] {
+                blockers[after] -= 1;
+                if blockers[after] == 0 {
+                    ready.push(Reverse(after));
+                }
+            }
+        }
+        reordered.extend(
+            (0..update.len())
+                .filter(|idx| !placed[*idx])
+                .map(|idx| update[idx]),
+        );
+        reordered
+    }
+}
+
+pub fn middle_page(update: &[u32]) -> u32 {
+    update[update.len() / 2]
+}
+
+fn read_page(data: &mut ByteCursor) -> Result<u32> {
+    let checkpoint = data.checkpoint();
+    if let Some(page) = data
+        .read_unsigned()
+        .and_then(|page| u32::try_from(page).ok())
+    {
+        return Ok(page);
+    }
+
+    data.rewind(checkpoint);
+    let len = data.skip_while(|b| b.is_ascii_digit());
+    let token = String::from_utf8_lossy(&data.data()[data.cursor - len..data.cursor]).into_owned();
+    data.rewind(checkpoint);
+    if len == 0 {
+        Err(unexpected(data))
+    } else {
+        Err(data.error(ParseErrorKind::MalformedNumber(token)))
+    }
+}
+
+fn expect_byte(data: &mut ByteCursor, byte: u8) -> Result<()> {
+    if data.expect_literal(&[byte]) {
+        Ok(())
+    } else {
+        Err(unexpected(data))
+    }
+}
+
+/// Consumes a `\n` or `\r\n`, returning whether there was one.
+fn line_ending(data: &mut ByteCursor) -> bool {
+    data.expect_literal(b"\n") || data.expect_literal(b"\r\n")
+}
+
+fn end_line(data: &mut ByteCursor) -> Result<()> {
+    if data.eof() || line_ending(data) {
+        Ok(())
+    } else {
+        Err(unexpected(data))
+    }
+}
+
+fn unexpected(data: &ByteCursor) -> ParseError {
+    match data.peek() {
+        Some(b) => data.error(ParseErrorKind::UnexpectedByte(*b)),
+        None => data.error(ParseErrorKind::UnexpectedEnd),
+    }
+}
+
+#[cfg(test)]
+mod day_five {
+    use super::*;
+    use aoc_common::Position;
+
+    #[test]
+    fn example_data_load() {
+        let queue = PrintQueue::parse_data(example_data()).unwrap();
+        assert_eq!(21, queue.rules.len());
+        assert!(queue.rules.contains(&(47, 53)));
+        assert!(queue.rules.contains(&(53, 13)));
+        assert_eq!(6, queue.updates.len());
+        assert_eq!(vec![75, 47, 61, 53, 29], queue.updates[0]);
+        assert_eq!(vec![97, 13, 75, 29, 47], queue.updates[5]);
+    }
+
+    #[test]
+    fn example_data_is_ordered() {
+        let queue = PrintQueue::parse_data(example_data()).unwrap();
+        let ordered: Vec<bool> = queue
+            .updates
+            .iter()
+            .map(|update| queue.is_ordered(update))
+            .collect();
+        assert_eq!(vec![true, true, true, false, false, false], ordered);
+    }
+
+    #[test]
+    fn example_data_reorder() {
+        let queue = PrintQueue::parse_data(example_data()).unwrap();
+        assert_eq!(vec![97, 75, 47, 61, 53], queue.reorder(&queue.updates[3]));
+        assert_eq!(vec![61, 29, 13], queue.reorder(&queue.updates[4]));
+        assert_eq!(vec![97, 75, 47, 29, 13], queue.reorder(&queue.updates[5]));
+    }
+
+    #[test]
+    fn reorder_sparse_rules() {
+        // Rules only between some pairs, always from the lower page to the
+        // higher, so no comparison sort could be given a total order.
+        let rules: HashSet<(u32, u32)> = (1..=41)
+            .flat_map(|a| (a + 1..=41).map(move |b| (a, b)))
+            .filter(|(a, b)| (a * 7 + b) % 5 == 0)
+            .collect();
+        let queue = PrintQueue {
+            rules,
+            updates: Vec::new(),
+        };
+        let update: Vec<u32> = (1..=41).rev().collect();
+        let reordered = queue.reorder(&update);
+        assert!(queue.is_ordered(&reordered));
+        let mut pages = reordered.clone();
+        pages.sort();
+        assert_eq!((1..=41).collect::<Vec<_>>(), pages);
+
+        // Pages with no rules between them stay where they were.
+        assert_eq!(vec![9, 4, 6], queue.reorder(&[9, 4, 6]));
+    }
+
+    #[test]
+    fn example_data_solution() {
+        let queue = DayFive::parse(&example_data()).unwrap();
+        assert_eq!(Answer::from(143), DayFive::part_one(&queue));
+        assert_eq!(Answer::from(123), DayFive::part_two(&queue));
+    }
+
+    #[test]
+    fn parse_line_endings() {
+        let queue = DayFive::parse(b"47|53\r\n97|13\r\n\r\n47,53\r\n97,13\r\n").unwrap();
+        assert_eq!(HashSet::from([(47, 53), (97, 13)]), queue.rules);
+        assert_eq!(vec![vec![47, 53], vec![97, 13]], queue.updates);
+
+        let queue = DayFive::parse(b"47|53\n\n47,53\n\n\r\n").unwrap();
+        assert_eq!(vec![vec![47, 53]], queue.updates);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = PrintQueue::parse_data(b"47|53\n97-13\n".to_vec()).unwrap_err();
+        assert_eq!(ParseErrorKind::UnexpectedByte(b'-'), err.kind);
+        assert_eq!(Some(Position::new(2, 3)), err.position);
+
+        let err = PrintQueue::parse_data(b"47|53\n\n75,,47\n".to_vec()).unwrap_err();
+        assert_eq!(ParseErrorKind::UnexpectedByte(b','), err.kind);
+        assert_eq!(Some(Position::new(3, 4)), err.position);
+
+        let err = PrintQueue::parse_data(b"47|99999999999\n".to_vec()).unwrap_err();
+        assert_eq!(
+            ParseErrorKind::MalformedNumber(String::from("99999999999")),
+            err.kind
+        );
+        assert_eq!(Some(Position::new(1, 4)), err.position);
+    }
+
+    fn example_data() -> Vec<u8> {
+        b"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+"
+        .into()
+    }
+}
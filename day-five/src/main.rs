@@ -0,0 +1,29 @@
+use aoc_common::{cli, read_input, Result, Solution};
+use day_five::DayFive;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    cli::exit_code(run())
+}
+
+fn run() -> Result<()> {
+    let data = read_input(DayFive::INPUT)?;
+    let queue = DayFive::parse(&data)?;
+    let ordered = queue
+        .updates
+        .iter()
+        .filter(|update| queue.is_ordered(update))
+        .count();
+
+    println!(
+        "{ordered} updates are correctly ordered, their middle pages add up to {}",
+        DayFive::part_one(&queue)
+    );
+    println!(
+        "{} updates needed reordering, their middle pages add up to {}",
+        queue.updates.len() - ordered,
+        DayFive::part_two(&queue)
+    );
+    println!("Merry Christmas");
+    Ok(())
+}
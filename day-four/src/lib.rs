@@ -0,0 +1,303 @@
+use aoc_common::{Answer, Cords, Grid, Result, Solution, NEIGHBORS_8};
+
+pub struct DayFour;
+
+impl Solution for DayFour {
+    type Parsed = WordSearch;
+
+    const DAY: u8 = 4;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/data.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        WordSearch::parse_data(input.to_vec())
+    }
+
+    fn part_one(word_search: &Self::Parsed) -> Answer {
+        word_search.count_word(b"XMAS").into()
+    }
+
+    fn part_two(word_search: &Self::Parsed) -> Answer {
+        word_search
+            .find_stencil_rotations(&Stencil::x_mas())
+            .len()
+            .into()
+    }
+}
+
+pub struct WordSearch {
+    pub grid: Grid<u8>,
+}
+
+impl WordSearch {
+    pub fn parse_data(raw_data: Vec<u8>) -> Result<Self> {
+        Ok(Self {
+            grid: Grid::parse(&raw_data)?,
+        })
+    }
+
+    /// Counts every occurrence of `word` read in a straight line in any of
+    /// the eight directions, including backwards and diagonally.
+    pub fn count_word(&self, word: &[u8]) -> usize {
+        let Some(first) = word.first() else {
+            return 0;
+        };
+        let directions: &[(isize, isize)] = if word.len() == 1 {
+            &NEIGHBORS_8[..1]
+        } else {
+            &NEIGHBORS_8
+        };
+
+        self.grid
+            .iter()
+            .filter(|(_, letter)| *letter == first)
+            .map(|(pos, _)| {
+                directions
+                    .iter()
+                    .filter(|&&dir| self.word_at(word, pos, dir))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Returns the top-left corner of every place `stencil` matches.
+    pub fn find_stencil(&self, stencil: &Stencil) -> Vec<Cords> {
+        let (width, height) = (stencil.cells.width(), stencil.cells.height());
+        if width > self.grid.width() || height > self.grid.height() {
+            return Vec::new();
+        }
+
+        (0..=self.grid.height() - height)
+            .flat_map(|y| (0..=self.grid.width() - width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                stencil.cells.iter().all(|((sx, sy), cell)| {
+                    cell.is_none_or(|letter| self.grid[(x + sx, y + sy)] == letter)
+                })
+            })
+            .collect()
+    }
+
+    /// Matches `stencil` in each of its distinct rotations, returning the
+    /// top-left corner of every match along with the index of the rotation
+    /// in [`Stencil::rotations`] that matched. With wildcards, several
+    /// rotations can match at the same corner, each counting as its own
+    /// match.
+    pub fn find_stencil_rotations(&self, stencil: &Stencil) -> Vec<(usize, Cords)> {
+        stencil
+            .rotations()
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, rotation)| {
+                self.find_stencil(rotation)
+                    .into_iter()
+                    .map(move |pos| (idx, pos))
+            })
+            .collect()
+    }
+
+    fn word_at(&self, word: &[u8], pos: Cords, (dx, dy): (isize, isize)) -> bool {
+        word.iter().enumerate().all(|(idx, letter)| {
+            let idx = idx as isize;
+            self.grid
+                .offset(pos, (dx * idx, dy * idx))
+                .is_some_and(|cords| self.grid[cords] == *letter)
+        })
+    }
+}
+
+/// A rectangular pattern where `None` cells match any letter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stencil {
+    pub cells: Grid<Option<u8>>,
+}
+
+impl Stencil {
+    /// Builds a stencil from newline separated rows, treating `wildcard` as a
+    /// cell that matches anything.
+    pub fn parse(pattern: &[u8], wildcard: u8) -> Result<Self> {
+        let cells = Grid::parse(pattern)?.map(|&b| (b != wildcard).then_some(b));
+        Ok(Self { cells })
+    }
+
+    /// Two `MAS` crossing in an X, the shape part two looks for:
+    ///
+    /// ```text
+    /// M.S
+    /// .A.
+    /// M.S
+    /// ```
+    pub fn x_mas() -> Self {
+        let (m, a, s) = (Some(b'M'), Some(b'A'), Some(b'S'));
+        Self {
+            cells: Grid::new(3, 3, vec![m, None, s, None, a, None, m, None, s]),
+        }
+    }
+
+    /// The stencil turned 90 degrees clockwise.
+    pub fn rotate(&self) -> Self {
+        let height = self.cells.height();
+        let cells = Grid::from_fn(height, self.cells.width(), |(x, y)| {
+            self.cells[(y, height - 1 - x)]
+        });
+        Self { cells }
+    }
+
+    /// Every distinct rotation of the stencil, starting with itself.
+    pub fn rotations(&self) -> Vec<Self> {
+        let mut rotations = vec![self.clone()];
+        for _ in 0..3 {
+            let next = rotations[rotations.len() - 1].rotate();
+            if !rotations.contains(&next) {
+                rotations.push(next);
+            }
+        }
+        rotations
+    }
+}
+
+#[cfg(test)]
+mod day_four {
+    use super::*;
+
+    #[test]
+    fn example_one_data_load() {
+        let word_search = WordSearch::parse_data(example_one_data()).unwrap();
+        assert_eq!(6, word_search.grid.width());
+        assert_eq!(5, word_search.grid.height());
+        assert_eq!(
+            "..X...\n.SAMX.\n.A..A.\nXMAS.S\n.X...Z",
+            word_search.grid.to_string()
+        );
+    }
+
+    #[test]
+    fn example_two_data_load() {
+        let word_search = WordSearch::parse_data(example_two_data()).unwrap();
+        assert_eq!(10, word_search.grid.width());
+        assert_eq!(10, word_search.grid.height());
+    }
+
+    #[test]
+    fn example_one_data_get_byte_last() {
+        let data = WordSearch::parse_data(example_one_data()).unwrap();
+        assert_eq!(Some(&b'Z'), data.grid.get(5, 4));
+    }
+
+    #[test]
+    fn example_one_data_get_byte_first_s() {
+        let data = WordSearch::parse_data(example_one_data()).unwrap();
+        assert_eq!(Some(&b'S'), data.grid.get(1, 1));
+    }
+
+    #[test]
+    fn example_one_count_word() {
+        let word_search = WordSearch::parse_data(example_one_data()).unwrap();
+        assert_eq!(4, word_search.count_word(b"XMAS"));
+    }
+
+    #[test]
+    fn example_two_count_word() {
+        let word_search = WordSearch::parse_data(example_two_data()).unwrap();
+        assert_eq!(18, word_search.count_word(b"XMAS"));
+        assert_eq!(Answer::from(18), DayFour::part_one(&word_search));
+    }
+
+    #[test]
+    fn count_word_edge_cases() {
+        let word_search = WordSearch::parse_data(b"AB\nBA".to_vec()).unwrap();
+        assert_eq!(0, word_search.count_word(b""));
+        assert_eq!(2, word_search.count_word(b"A"));
+        assert_eq!(4, word_search.count_word(b"AB"));
+        assert_eq!(0, word_search.count_word(b"ABA"));
+    }
+
+    #[test]
+    fn stencil_rotate() {
+        let stencil = Stencil::parse(b"AB.\nCDE", b'.').unwrap();
+        let rotated = stencil.rotate();
+        assert_eq!(2, rotated.cells.width());
+        assert_eq!(3, rotated.cells.height());
+        assert_eq!(
+            vec![
+                Some(b'C'),
+                Some(b'A'),
+                Some(b'D'),
+                Some(b'B'),
+                Some(b'E'),
+                None
+            ],
+            rotated
+                .cells
+                .iter()
+                .map(|(_, cell)| *cell)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(stencil, rotated.rotate().rotate().rotate());
+    }
+
+    #[test]
+    fn stencil_symmetric_rotations() {
+        let stencil = Stencil::parse(b"A.A\n.A.\nA.A", b'.').unwrap();
+        assert_eq!(1, stencil.rotations().len());
+        assert_eq!(
+            Stencil::parse(b"M.S\n.A.\nM.S", b'.').unwrap(),
+            Stencil::x_mas()
+        );
+        assert_eq!(4, Stencil::x_mas().rotations().len());
+    }
+
+    #[test]
+    fn example_two_find_stencil() {
+        let word_search = WordSearch::parse_data(example_two_data()).unwrap();
+        let stencil = Stencil::x_mas();
+        assert_eq!(vec![(1, 0), (1, 2)], word_search.find_stencil(&stencil));
+        assert_eq!(9, word_search.find_stencil_rotations(&stencil).len());
+        assert_eq!(Answer::from(9), DayFour::part_two(&word_search));
+    }
+
+    #[test]
+    fn find_stencil_rotations_share_corners() {
+        let word_search = WordSearch::parse_data(b"AAA\nAAA\nAAA".to_vec()).unwrap();
+        let stencil = Stencil::parse(b"A.\n..", b'.').unwrap();
+        let matches = word_search.find_stencil_rotations(&stencil);
+        assert_eq!(16, matches.len());
+        for rotation in 0..4 {
+            assert!(matches.contains(&(rotation, (1, 1))));
+        }
+
+        let word_search = WordSearch::parse_data(example_two_data()).unwrap();
+        let stencil = Stencil::x_mas();
+        let matches = word_search.find_stencil_rotations(&stencil);
+        assert!(matches.contains(&(0, (1, 0))));
+        assert!(matches.iter().all(|(rotation, _)| *rotation < 4));
+    }
+
+    #[test]
+    fn find_stencil_larger_than_grid() {
+        let word_search = WordSearch::parse_data(example_one_data()).unwrap();
+        let stencil = Stencil::parse(b"XXXXXXX", b'.').unwrap();
+        assert!(word_search.find_stencil(&stencil).is_empty());
+    }
+
+    fn example_one_data() -> Vec<u8> {
+        let data = b"..X...
+.SAMX.
+.A..A.
+XMAS.S
+.X...Z";
+        data.to_vec()
+    }
+
+    fn example_two_data() -> Vec<u8> {
+        let data = b"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+        data.to_vec()
+    }
+}
@@ -0,0 +1,75 @@
+//! Compares the original clone-and-sort day one solution with the linear
+//! time one on a generated input. The row count defaults to ten million and
+//! can be passed as the first argument:
+//!
+//! ```text
+//! cargo bench -p day-one --bench distance -- 1000000
+//! ```
+
+use aoc_common::testing::{size_arg, time, SeededRng};
+use day_one::{find_distance, linear_sort, sorted_similarity_score};
+use std::collections::HashMap;
+
+const DEFAULT_ROWS: usize = 10_000_000;
+
+/// The solution as it was before sorting in linear time: both lists cloned,
+/// sorted with the standard sort and the similarity counted through a
+/// `HashMap` rebuilt by `get` and `insert`.
+fn baseline(list_one: &[i32], list_two: &[i32]) -> (i64, i64) {
+    let mut sorted_one = list_one.to_vec();
+    let mut sorted_two = list_two.to_vec();
+    sorted_one.sort();
+    sorted_two.sort();
+    let distance = sorted_one.iter().enumerate().fold(0, |acc, (idx, val)| {
+        let other = sorted_two.get(idx).unwrap();
+        acc + i64::from(val.abs_diff(*other))
+    });
+
+    let summary: HashMap<i32, i64> = list_two.iter().fold(HashMap::new(), |mut acc, val| {
+        acc.insert(*val, acc.get(val).unwrap_or(&0) + 1);
+        acc
+    });
+    let similarity = list_one.iter().fold(0, |acc, val| {
+        acc + i64::from(*val) * summary.get(val).unwrap_or(&0)
+    });
+    (distance, similarity)
+}
+
+fn linear(mut list_one: Vec<i32>, mut list_two: Vec<i32>) -> (i64, i64) {
+    linear_sort(&mut list_one);
+    linear_sort(&mut list_two);
+    (
+        find_distance(&list_one, &list_two),
+        sorted_similarity_score(&list_one, &list_two),
+    )
+}
+
+/// Five digit location IDs like the puzzle input. Each list has its own
+/// seed, so every run sorts the same values.
+fn generate(rows: usize, seed: u64) -> Vec<i32> {
+    let mut rng = SeededRng::new(seed);
+    (0..rows)
+        .map(|_| 10_000 + rng.below(90_000) as i32)
+        .collect()
+}
+
+fn main() {
+    let rows = size_arg(DEFAULT_ROWS);
+    let list_one = generate(rows, 1);
+    let list_two = generate(rows, 2);
+
+    let (expected, baseline_time) = time(|| baseline(&list_one, &list_two));
+    let (actual, linear_time) = time(|| linear(list_one, list_two));
+    assert_eq!(expected, actual, "implementations disagree");
+
+    println!(
+        "{rows} rows: distance {}, similarity {}",
+        actual.0, actual.1
+    );
+    println!("baseline: {baseline_time:>12.2?}");
+    println!("linear:   {linear_time:>12.2?}");
+    println!(
+        "speedup:  {:>11.1}x",
+        baseline_time.as_secs_f64() / linear_time.as_secs_f64()
+    );
+}
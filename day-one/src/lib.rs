@@ -0,0 +1,657 @@
+use aoc_common::{read_input, Answer, ParseError, ParseErrorKind, Position, Result, Solution};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    /// Both lists, sorted.
+    type Parsed = (Vec<i32>, Vec<i32>);
+
+    const DAY: u8 = 1;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/first_input.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        let (mut list_one, mut list_two) = parse_lists(&String::from_utf8_lossy(input))?;
+        linear_sort(&mut list_one);
+        linear_sort(&mut list_two);
+        Ok((list_one, list_two))
+    }
+
+    fn part_one((list_one, list_two): &Self::Parsed) -> Answer {
+        find_distance(list_one, list_two).into()
+    }
+
+    fn part_two((list_one, list_two): &Self::Parsed) -> Answer {
+        sorted_similarity_score(list_one, list_two).into()
+    }
+}
+
+/// Sums each value of `list_one` times the number of times it appears in
+/// `list_two`. Neither list needs to be sorted.
+pub fn find_similaritiy_score(list_one: &[i32], list_two: &[i32]) -> i64 {
+    let mut list_two_summary: HashMap<i32, i64> = HashMap::with_capacity(list_two.len());
+    for val in list_two {
+        *list_two_summary.entry(*val).or_default() += 1;
+    }
+
+    list_one
+        .iter()
+        .map(|val| i64::from(*val) * list_two_summary.get(val).copied().unwrap_or(0))
+        .sum()
+}
+
+/// [`find_similaritiy_score`] for two sorted lists, walking both in step
+/// instead of building a lookup table.
+pub fn sorted_similarity_score(list_one: &[i32], list_two: &[i32]) -> i64 {
+    // Each value's run is counted by scanning forward over it, so every
+    // element is looked at once.
+    let run = |list: &[i32], value: &i32| list.iter().take_while(|val| *val == value).count();
+    let (mut one, mut two) = (list_one, list_two);
+    let mut score = 0;
+    while let (Some(a), Some(b)) = (one.first(), two.first()) {
+        match a.cmp(b) {
+            Ordering::Less => one = &one[run(one, a)..],
+            Ordering::Greater => two = &two[run(two, b)..],
+            Ordering::Equal => {
+                let one_count = run(one, a);
+                let two_count = run(two, b);
+                score += i64::from(*a) * (one_count * two_count) as i64;
+                one = &one[one_count..];
+                two = &two[two_count..];
+            }
+        }
+    }
+    score
+}
+
+/// Sums the distance between each pair of values, pairing the lists up in
+/// order. Both lists are expected to be sorted. Values past the end of the
+/// shorter list are ignored, see [`distance_with`] for the other options.
+pub fn find_distance(list_one: &[i32], list_two: &[i32]) -> i64 {
+    list_one
+        .iter()
+        .zip(list_two)
+        .map(|(a, b)| i64::from(a.abs_diff(*b)))
+        .sum()
+}
+
+/// How [`distance_with`] treats lists of different lengths.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DistanceMode {
+    /// The lists must be the same length.
+    #[default]
+    Strict,
+    /// Values past the end of the shorter list are ignored.
+    Truncate,
+    /// Each value past the end of the shorter list adds a fixed cost.
+    Penalise(u32),
+    /// Every value of the shorter list is matched with a distinct value of
+    /// the longer one, leaving out whichever values give the smallest total.
+    Optimal,
+}
+
+impl FromStr for DistanceMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.split_once('=') {
+            None if s == "strict" => Ok(Self::Strict),
+            None if s == "truncate" => Ok(Self::Truncate),
+            None if s == "optimal" => Ok(Self::Optimal),
+            Some(("penalise", cost)) => cost
+                .parse()
+                .map(Self::Penalise)
+                .map_err(|_| format!("`{cost}` is not a valid penalty")),
+            _ => Err(format!(
+                "unknown distance mode `{s}`, expected strict, truncate, penalise=<COST> or optimal"
+            )),
+        }
+    }
+}
+
+/// The most steps [`DistanceMode::Optimal`] may take, a few seconds' work.
+pub const OPTIMAL_STEP_LIMIT: u128 = 1 << 31;
+
+/// Why [`distance_with`] couldn't work out a distance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DistanceError {
+    /// [`DistanceMode::Strict`] was given lists of different lengths.
+    LengthMismatch { list_one: usize, list_two: usize },
+    /// [`DistanceMode::Optimal`] would take more than [`OPTIMAL_STEP_LIMIT`]
+    /// steps on lists this long.
+    TooLargeForOptimal { list_one: usize, list_two: usize },
+}
+
+impl fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { list_one, list_two } => {
+                write!(
+                    f,
+                    "list one has {list_one} values but list two has {list_two}"
+                )
+            }
+            Self::TooLargeForOptimal { list_one, list_two } => write!(
+                f,
+                "optimal matching of {list_one} values against {list_two} would take over \
+                 {OPTIMAL_STEP_LIMIT} steps, try truncate or penalise=<N> instead"
+            ),
+        }
+    }
+}
+
+impl error::Error for DistanceError {}
+
+/// [`find_distance`] with an explicit rule for lists of different lengths.
+/// Both lists are expected to be sorted.
+pub fn distance_with(
+    list_one: &[i32],
+    list_two: &[i32],
+    mode: DistanceMode,
+) -> std::result::Result<i64, DistanceError> {
+    let unmatched = list_one.len().abs_diff(list_two.len());
+    let shorter = list_one.len().min(list_two.len());
+    match mode {
+        DistanceMode::Strict if unmatched > 0 => Err(DistanceError::LengthMismatch {
+            list_one: list_one.len(),
+            list_two: list_two.len(),
+        }),
+        DistanceMode::Optimal if shorter as u128 * (unmatched as u128 + 1) > OPTIMAL_STEP_LIMIT => {
+            Err(DistanceError::TooLargeForOptimal {
+                list_one: list_one.len(),
+                list_two: list_two.len(),
+            })
+        }
+        DistanceMode::Strict | DistanceMode::Truncate => Ok(find_distance(list_one, list_two)),
+        DistanceMode::Penalise(cost) => {
+            Ok(find_distance(list_one, list_two) + i64::from(cost) * unmatched as i64)
+        }
+        DistanceMode::Optimal => Ok(optimal_distance(list_one, list_two)),
+    }
+}
+
+/// The smallest total distance from matching every value of the shorter
+/// sorted list with a distinct value of the longer one.
+///
+/// With sorted lists some optimal matching keeps both in order, so only the
+/// number of values skipped so far on the longer list matters: `best[skips]`
+/// is the cheapest way to match the values seen so far with that many
+/// skipped. This takes O(shorter * (difference + 1)) time, which is linear
+/// when the lengths are close but quadratic at worst, so [`distance_with`]
+/// refuses lists past [`OPTIMAL_STEP_LIMIT`].
+pub fn optimal_distance(list_one: &[i32], list_two: &[i32]) -> i64 {
+    let (short, long) = if list_one.len() <= list_two.len() {
+        (list_one, list_two)
+    } else {
+        (list_two, list_one)
+    };
+    let spare = long.len() - short.len();
+    let mut best = vec![0i64; spare + 1];
+
+    for (idx, val) in short.iter().enumerate() {
+        best[0] += i64::from(val.abs_diff(long[idx]));
+        for skips in 1..=spare {
+            let matched = best[skips] + i64::from(val.abs_diff(long[idx + skips]));
+            best[skips] = matched.min(best[skips - 1]);
+        }
+    }
+    best[spare]
+}
+
+/// Sorts `values` in linear time. A counting sort is used when the values
+/// span no more than the list length, otherwise an LSD radix sort.
+pub fn linear_sort(values: &mut [i32]) {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return;
+    };
+    let span = max.abs_diff(*min) as usize + 1;
+    if span <= values.len().max(RADIX_BUCKETS) {
+        counting_sort(values, *min, span);
+    } else {
+        radix_sort(values);
+    }
+}
+
+const RADIX_BITS: u32 = 11;
+const RADIX_BUCKETS: usize = 1 << RADIX_BITS;
+
+fn counting_sort(values: &mut [i32], min: i32, span: usize) {
+    let mut counts = vec![0usize; span];
+    for val in values.iter() {
+        counts[val.abs_diff(min) as usize] += 1;
+    }
+    let mut idx = 0;
+    for (offset, count) in counts.into_iter().enumerate() {
+        values[idx..idx + count].fill(min.wrapping_add_unsigned(offset as u32));
+        idx += count;
+    }
+}
+
+fn radix_sort(values: &mut [i32]) {
+    // Flipping the sign bit makes unsigned order match signed order.
+    let mut keys: Vec<u32> = values.iter().map(|val| *val as u32 ^ 1 << 31).collect();
+    let mut scratch = vec![0u32; keys.len()];
+
+    for shift in (0..u32::BITS).step_by(RADIX_BITS as usize) {
+        let bucket = |key: u32| (key >> shift) as usize & (RADIX_BUCKETS - 1);
+        let mut offsets = [0usize; RADIX_BUCKETS];
+        for key in &keys {
+            offsets[bucket(*key)] += 1;
+        }
+        if offsets.contains(&keys.len()) {
+            continue;
+        }
+        let mut total = 0;
+        for offset in offsets.iter_mut() {
+            (*offset, total) = (total, total + *offset);
+        }
+        for key in &keys {
+            let bucket = bucket(*key);
+            scratch[offsets[bucket]] = *key;
+            offsets[bucket] += 1;
+        }
+        std::mem::swap(&mut keys, &mut scratch);
+    }
+
+    for (val, key) in values.iter_mut().zip(keys) {
+        *val = (key ^ 1 << 31) as i32;
+    }
+}
+
+pub fn load_file(file_path: String) -> Result<(Vec<i32>, Vec<i32>)> {
+    let data = read_input(file_path)?;
+    parse_lists(&String::from_utf8_lossy(&data))
+}
+
+pub fn load_columns(file_path: String) -> Result<Vec<Vec<i32>>> {
+    let data = read_input(file_path)?;
+    parse_columns(&String::from_utf8_lossy(&data))
+}
+
+/// Loads a single list with one value per line, which unlike the two column
+/// input may be any length.
+pub fn load_list(file_path: String) -> Result<Vec<i32>> {
+    let data = read_input(file_path)?;
+    let mut columns = parse_table(&String::from_utf8_lossy(&data), Some(1))?;
+    Ok(columns.pop().unwrap_or_default())
+}
+
+/// A square table of scores comparing every column with every other one.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PairwiseMatrix {
+    pub scores: Vec<Vec<i64>>,
+}
+
+impl PairwiseMatrix {
+    /// Scores every ordered pair of `columns` with `score`.
+    pub fn new<F: Fn(&[i32], &[i32]) -> i64>(columns: &[Vec<i32>], score: F) -> Self {
+        let scores = columns
+            .iter()
+            .map(|a| columns.iter().map(|b| score(a, b)).collect())
+            .collect();
+        Self { scores }
+    }
+
+    /// The distance between each pair of columns, pairing them up smallest
+    /// to smallest.
+    pub fn distances(columns: &[Vec<i32>]) -> Self {
+        let sorted: Vec<Vec<i32>> = columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                linear_sort(&mut column);
+                column
+            })
+            .collect();
+        Self::new(&sorted, find_distance)
+    }
+
+    /// The similarity score of each column against each other column.
+    pub fn similarities(columns: &[Vec<i32>]) -> Self {
+        Self::new(columns, find_similaritiy_score)
+    }
+}
+
+impl fmt::Display for PairwiseMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .scores
+            .iter()
+            .flatten()
+            .map(|score| score.to_string().len())
+            .chain([format!("#{}", self.scores.len()).len()])
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:width$}", "")?;
+        for column in 1..=self.scores.len() {
+            write!(f, "  {:>width$}", format!("#{column}"))?;
+        }
+        for (row, scores) in self.scores.iter().enumerate() {
+            write!(f, "\n{:<width$}", format!("#{}", row + 1))?;
+            for score in scores {
+                write!(f, "  {score:>width$}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads two whitespace separated columns of integers. Any run of spaces or
+/// tabs separates the columns, `\r\n` line endings are accepted and blank
+/// lines are skipped.
+pub fn parse_lists(lines: &str) -> Result<(Vec<i32>, Vec<i32>)> {
+    let mut columns = parse_table(lines, Some(2))?.into_iter();
+    let list_one = columns.next().unwrap_or_default();
+    let list_two = columns.next().unwrap_or_default();
+    Ok((list_one, list_two))
+}
+
+/// Reads any number of whitespace separated columns of integers, following
+/// the same rules as [`parse_lists`]. The first row sets how many columns
+/// every other row must have.
+pub fn parse_columns(lines: &str) -> Result<Vec<Vec<i32>>> {
+    parse_table(lines, None)
+}
+
+fn parse_table(lines: &str, width: Option<usize>) -> Result<Vec<Vec<i32>>> {
+    let mut columns: Vec<Vec<i32>> = width.map_or_else(Vec::new, |width| vec![vec![]; width]);
+    let mut width = width;
+
+    for (line_idx, line) in lines.lines().enumerate() {
+        let position = |offset: usize| Position::new(line_idx + 1, offset + 1);
+        let row: Vec<(usize, &str)> = tokens(line).collect();
+        if row.is_empty() {
+            continue;
+        }
+        let expected = *width.get_or_insert(row.len());
+        if columns.is_empty() {
+            columns = vec![vec![]; expected];
+        }
+        if row.len() != expected {
+            let offset = row
+                .get(expected)
+                .map_or(line.trim_end().len(), |(offset, _)| *offset);
+            return Err(ParseError::new(
+                ParseErrorKind::RaggedRow {
+                    expected,
+                    found: row.len(),
+                },
+                position(offset),
+            ));
+        }
+        for (column, (offset, token)) in columns.iter_mut().zip(row) {
+            column.push(parse_value(token, position(offset))?);
+        }
+    }
+    Ok(columns)
+}
+
+/// Splits `line` on ASCII whitespace, yielding each token with its byte
+/// offset.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(|c: char| c.is_ascii_whitespace())
+        .scan(0, |offset, token| {
+            let start = *offset;
+            *offset += token.len() + 1;
+            Some((start, token))
+        })
+        .filter(|(_, token)| !token.is_empty())
+}
+
+fn parse_value(token: &str, position: Position) -> Result<i32> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(ParseErrorKind::MalformedNumber(token.to_string()), position))
+}
+
+#[cfg(test)]
+mod day_one {
+    use crate::{
+        distance_with, find_distance, find_similaritiy_score, linear_sort, optimal_distance,
+        parse_columns, parse_lists, sorted_similarity_score, DayOne, DistanceError, DistanceMode,
+        PairwiseMatrix,
+    };
+    use aoc_common::testing::SeededRng;
+    use aoc_common::{Answer, ParseErrorKind, Position, Solution};
+
+    #[test]
+    fn test_similarity() {
+        let list_one: Vec<i32> = vec![3, 4, 2, 1, 3, 3];
+        let list_two: Vec<i32> = vec![4, 3, 5, 3, 9, 3];
+
+        let similarity_score = find_similaritiy_score(&list_one, &list_two);
+
+        assert_eq!(similarity_score, 31);
+    }
+
+    #[test]
+    fn test_sorted_scores() {
+        let mut list_one: Vec<i32> = vec![3, 4, 2, 1, 3, 3];
+        let mut list_two: Vec<i32> = vec![4, 3, 5, 3, 9, 3];
+        linear_sort(&mut list_one);
+        linear_sort(&mut list_two);
+
+        assert_eq!(vec![1, 2, 3, 3, 3, 4], list_one);
+        assert_eq!(11, find_distance(&list_one, &list_two));
+        assert_eq!(31, sorted_similarity_score(&list_one, &list_two));
+        assert_eq!(0, sorted_similarity_score(&[], &list_two));
+    }
+
+    #[test]
+    fn test_distance_modes() {
+        let list_one = [1, 2, 3, 4];
+        let list_two = [3, 4, 5];
+        assert_eq!(
+            Err(DistanceError::LengthMismatch {
+                list_one: 4,
+                list_two: 3
+            }),
+            distance_with(&list_one, &list_two, DistanceMode::Strict)
+        );
+        assert_eq!(
+            Ok(6),
+            distance_with(&list_one, &list_two, DistanceMode::Truncate)
+        );
+        assert_eq!(
+            Ok(11),
+            distance_with(&list_one, &list_two, DistanceMode::Penalise(5))
+        );
+        assert_eq!(
+            Ok(3),
+            distance_with(&list_one, &list_two, DistanceMode::Optimal)
+        );
+        assert_eq!(
+            Ok(3),
+            distance_with(&list_two, &list_one, DistanceMode::Optimal)
+        );
+        assert_eq!(
+            Ok(6),
+            distance_with(&list_one[..3], &list_two, DistanceMode::Strict)
+        );
+    }
+
+    #[test]
+    fn test_optimal_distance_limit() {
+        // Only the lengths are checked, so the lists can be left unfilled.
+        let short = vec![0; 1 << 16];
+        let long = vec![0; 1 << 17];
+        let err = distance_with(&short, &long, DistanceMode::Optimal).unwrap_err();
+        assert_eq!(
+            DistanceError::TooLargeForOptimal {
+                list_one: 1 << 16,
+                list_two: 1 << 17
+            },
+            err
+        );
+        assert!(err.to_string().contains("try truncate"));
+        assert_eq!(
+            Ok(0),
+            distance_with(&short, &long[..(1 << 16) + 10], DistanceMode::Optimal)
+        );
+    }
+
+    #[test]
+    fn test_distance_mode_from_str() {
+        assert_eq!(Ok(DistanceMode::Optimal), "optimal".parse());
+        assert_eq!(Ok(DistanceMode::Penalise(7)), "penalise=7".parse());
+        assert!("penalise=x".parse::<DistanceMode>().is_err());
+        assert!("penalise".parse::<DistanceMode>().is_err());
+        assert!("closest".parse::<DistanceMode>().is_err());
+    }
+
+    /// Tries every way of leaving values out of the longer list.
+    fn brute_force_optimal(short: &[i32], long: &[i32]) -> i64 {
+        match (short.split_first(), long.split_first()) {
+            (None, _) => 0,
+            (Some(_), None) => i64::MAX,
+            (Some((a, short_rest)), Some((b, long_rest))) => {
+                let matched = brute_force_optimal(short_rest, long_rest)
+                    .saturating_add(i64::from(a.abs_diff(*b)));
+                if long.len() > short.len() {
+                    matched.min(brute_force_optimal(short, long_rest))
+                } else {
+                    matched
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimal_distance() {
+        let mut rng = SeededRng::new(0x2024_1220);
+        let mut random = move |modulus: u64| rng.below(modulus) as i32;
+
+        for _ in 0..500 {
+            let mut short: Vec<i32> = (0..random(5)).map(|_| random(20) - 10).collect();
+            let mut long: Vec<i32> = (0..short.len() as i32 + random(4))
+                .map(|_| random(20) - 10)
+                .collect();
+            short.sort();
+            long.sort();
+            let expected = brute_force_optimal(&short, &long);
+            assert_eq!(
+                expected,
+                optimal_distance(&short, &long),
+                "{short:?} {long:?}"
+            );
+            assert_eq!(
+                expected,
+                optimal_distance(&long, &short),
+                "{short:?} {long:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_linear_sort() {
+        let mut rng = SeededRng::new(0x2024_1201);
+        let mut random = move || rng.next_u32() as i32;
+
+        for (len, modulus) in [(0, 1), (1, 10), (50, 10), (1000, 100_000), (5000, i32::MAX)] {
+            let mut values: Vec<i32> = (0..len).map(|_| random() % modulus).collect();
+            values.extend([i32::MIN, i32::MAX, 0, -1].iter().take(len.min(4)));
+            let mut expected = values.clone();
+            expected.sort();
+            linear_sort(&mut values);
+            assert_eq!(expected, values, "{len} values below {modulus}");
+
+            let other: Vec<i32> = (0..len).map(|_| random() % modulus.min(50)).collect();
+            let mut sorted_other = other.clone();
+            linear_sort(&mut sorted_other);
+            assert_eq!(
+                find_similaritiy_score(&values, &other),
+                sorted_similarity_score(&values, &sorted_other)
+            );
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let parsed = DayOne::parse(b"3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        assert_eq!(Answer::from(11), DayOne::part_one(&parsed));
+        assert_eq!(Answer::from(31), DayOne::part_two(&parsed));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_lists("3   4\n4   x3\n").unwrap_err();
+        assert_eq!(
+            ParseErrorKind::MalformedNumber(String::from("x3")),
+            err.kind
+        );
+        assert_eq!(Some(Position::new(2, 5)), err.position);
+
+        let err = parse_lists("3   4\n4  \n").unwrap_err();
+        assert_eq!(
+            ParseErrorKind::RaggedRow {
+                expected: 2,
+                found: 1
+            },
+            err.kind
+        );
+        assert_eq!(Some(Position::new(2, 2)), err.position);
+
+        let err = parse_lists("3   4\n\n4 3 9 1\n").unwrap_err();
+        assert_eq!(
+            ParseErrorKind::RaggedRow {
+                expected: 2,
+                found: 4
+            },
+            err.kind
+        );
+        assert_eq!(Some(Position::new(3, 5)), err.position);
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let columns = parse_columns("3 4 1\n4 3 1\n\n2 5 9\n").unwrap();
+        assert_eq!(vec![vec![3, 4, 2], vec![4, 3, 5], vec![1, 1, 9]], columns);
+        assert_eq!(vec![vec![1], vec![2]], parse_columns("1 2").unwrap());
+        assert!(parse_columns("").unwrap().is_empty());
+
+        let err = parse_columns("3 4 1\n4 3\n").unwrap_err();
+        assert_eq!(
+            ParseErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            },
+            err.kind
+        );
+        assert_eq!(Some(Position::new(2, 4)), err.position);
+    }
+
+    #[test]
+    fn test_pairwise_matrices() {
+        let columns = parse_columns("3 4 3\n4 3 4\n2 5 2\n1 3 1\n3 9 3\n3 3 3").unwrap();
+        let distances = PairwiseMatrix::distances(&columns);
+        assert_eq!(
+            vec![vec![0, 11, 0], vec![11, 0, 11], vec![0, 11, 0]],
+            distances.scores
+        );
+        let similarities = PairwiseMatrix::similarities(&columns);
+        assert_eq!(31, similarities.scores[0][1]);
+        assert_eq!(31, similarities.scores[2][1]);
+        assert_eq!(
+            "    #1  #2  #3\n#1   0  11   0\n#2  11   0  11\n#3   0  11   0",
+            distances.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        let expected = (vec![3, 4, 2], vec![4, 3, 5]);
+        assert_eq!(expected, parse_lists("3   4\n4   3\n2   5\n").unwrap());
+        assert_eq!(expected, parse_lists("3\t4\n4 3\n  2 \t 5").unwrap());
+        assert_eq!(
+            expected,
+            parse_lists("3   4\r\n4   3\r\n2   5\r\n").unwrap()
+        );
+        assert_eq!(expected, parse_lists("3 4\n\n4 3\n2 5\n\n \r\n\n").unwrap());
+        assert_eq!((vec![], vec![]), parse_lists("").unwrap());
+    }
+}
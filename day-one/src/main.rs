@@ -1,74 +1,109 @@
-use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use aoc_common::cli::{self, Args};
+use aoc_common::Solution;
+use day_one::{
+    distance_with, linear_sort, load_columns, load_list, sorted_similarity_score, DayOne,
+    DistanceMode, PairwiseMatrix,
+};
+use std::error::Error;
+use std::process::ExitCode;
 
-fn main() {
-    println!("Merry Christmas");
+const USAGE: &str = "Usage: day-one [--distance <MODE>] [--lists <LEFT> <RIGHT>]
 
-    let (mut list_one, mut list_two) = load_file(String::from("./day-one/input/first_input.txt"));
+Compares the two lists in the day's input, or every pair of columns when it
+has more than two. --lists reads each list from its own file, one value per
+line, so the lists may differ in length. MODE decides what happens then:
 
-    list_one.sort();
-    list_two.sort();
+  strict          refuse lists of different lengths (default)
+  truncate        ignore values past the end of the shorter list
+  penalise=<N>    add N for each value past the end of the shorter list
+  optimal         leave out whichever values give the smallest distance;
+                  takes time proportional to the shorter length times the
+                  difference in lengths, and lists needing more than about
+                  two billion steps are refused";
 
-    let list_distance = find_distance(list_one.clone(), list_two.clone());
-    let list_similarity_score = find_similaritiy_score(list_one, list_two);
-    println!("List distance: {}", list_distance);
-    println!("List similarity score: {}", list_similarity_score);
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    distance: DistanceMode,
+    lists: Option<(String, String)>,
 }
 
-fn find_similaritiy_score(list_one: Vec<i32>, list_two: Vec<i32>) -> i32 {
-    let list_two_summary: HashMap<i32, i32> =
-        list_two.into_iter().fold(HashMap::new(), |mut acc, val| {
-            acc.insert(val, acc.get(&val).unwrap_or(&0) + 1);
-            acc
-        });
-
-    list_one.into_iter().fold(0, |acc, val| {
-        acc + val * list_two_summary.get(&val).unwrap_or(&0)
-    })
+fn main() -> ExitCode {
+    cli::run_main(USAGE, parse_args, run)
 }
 
-fn find_distance(list_one: Vec<i32>, list_two: Vec<i32>) -> i32 {
-    list_one.iter().enumerate().fold(0, |acc, (idx, val)| {
-        let dist = if val > list_two.get(idx).unwrap() {
-            val - list_two.get(idx).unwrap()
-        } else {
-            list_two.get(idx).unwrap() - val
-        };
-        acc + dist
-    })
+/// Returns `None` when help was asked for.
+fn parse_args<I: Iterator<Item = String>>(
+    mut args: Args<I>,
+) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--distance" => options.distance = args.value(&arg)?.parse()?,
+            "--lists" => options.lists = Some((args.value(&arg)?, args.value(&arg)?)),
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+    Ok(Some(options))
 }
 
-fn load_file(file_path: String) -> (Vec<i32>, Vec<i32>) {
-    let file_path = Path::new(&file_path);
-    let mut list_one: Vec<i32> = vec![];
-    let mut list_two: Vec<i32> = vec![];
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    println!("Merry Christmas");
 
-    if let Ok(lines) = fs::read_to_string(file_path) {
-        let lines = lines.lines();
-        for line in lines {
-            if line.is_empty() {
-                break;
+    let (mut list_one, mut list_two) = match &options.lists {
+        Some((left, right)) => (load_list(left.clone())?, load_list(right.clone())?),
+        None => {
+            let columns = load_columns(String::from(DayOne::INPUT))?;
+            match <[Vec<i32>; 2]>::try_from(columns) {
+                Ok([list_one, list_two]) => (list_one, list_two),
+                Err(columns) => {
+                    println!("Distances\n{}", PairwiseMatrix::distances(&columns));
+                    println!(
+                        "\nSimilarity scores\n{}",
+                        PairwiseMatrix::similarities(&columns)
+                    );
+                    return Ok(());
+                }
             }
-            let mut line = line.split("   ");
-            list_one.push(line.next().unwrap().parse().unwrap());
-            list_two.push(line.next().unwrap().parse().unwrap());
         }
-    }
-    (list_one, list_two)
+    };
+
+    linear_sort(&mut list_one);
+    linear_sort(&mut list_two);
+
+    let list_distance = distance_with(&list_one, &list_two, options.distance)?;
+    let list_similarity_score = sorted_similarity_score(&list_one, &list_two);
+    println!("List distance: {}", list_distance);
+    println!("List similarity score: {}", list_similarity_score);
+    Ok(())
 }
 
 #[cfg(test)]
-mod day_one {
-    use crate::find_similaritiy_score;
+mod options {
+    use super::{parse_args, Options};
+    use aoc_common::cli::Args;
+    use day_one::DistanceMode;
 
     #[test]
-    fn test_similarity() {
-        let list_one: Vec<i32> = vec![3, 4, 2, 1, 3, 3];
-        let list_two: Vec<i32> = vec![4, 3, 5, 3, 9, 3];
-
-        let similarity_score = find_similaritiy_score(list_one, list_two);
-
-        assert_eq!(similarity_score, 31);
+    fn parse_args_modes() {
+        assert_eq!(
+            Ok(Some(Options::default())),
+            parse_args(Args::from_strs(&[]))
+        );
+        assert_eq!(
+            Ok(Some(Options {
+                distance: DistanceMode::Penalise(3),
+                lists: Some((String::from("a.txt"), String::from("b.txt"))),
+            })),
+            parse_args(Args::from_strs(&[
+                "--distance",
+                "penalise=3",
+                "--lists",
+                "a.txt",
+                "b.txt"
+            ]))
+        );
+        assert!(parse_args(Args::from_strs(&["--lists", "a.txt"])).is_err());
+        assert!(parse_args(Args::from_strs(&["--distance", "nearest"])).is_err());
     }
 }
@@ -0,0 +1,62 @@
+//! Compares searching for loop obstructions by re-walking the patrol one
+//! cell at a time with jumping between turns on a `JumpTable`, on a
+//! generated map. The map side defaults to 130 cells, like the puzzle input,
+//! and can be passed as the first argument:
+//!
+//! ```text
+//! cargo bench -p day-six --bench loops -- 400
+//! ```
+
+use aoc_common::testing::{size_arg, time, SeededRng};
+use aoc_common::{Cords, Grid};
+use day_six::{Guard, Patrol};
+
+const DEFAULT_SIDE: usize = 130;
+
+/// The search as it was before the jump table: every candidate walked cell
+/// by cell through `Guard::patrol`.
+fn baseline(guard: &Guard) -> Vec<Cords> {
+    let Patrol::Exits(path) = guard.patrol(None) else {
+        return Vec::new();
+    };
+    path.into_iter()
+        .filter(|cell| *cell != guard.pos && guard.patrol(Some(*cell)) == Patrol::Loops)
+        .collect()
+}
+
+/// A square map with obstacles about as dense as the puzzle input and the
+/// guard in the middle. Seeds are tried in turn from 1 until the guard's
+/// patrol leaves the map, so each size always gives the same map.
+fn generate(side: usize) -> Guard {
+    (1..)
+        .map(|seed| {
+            let mut rng = SeededRng::new(seed);
+            let cells = (0..side * side)
+                .map(|idx| match rng.below(100) {
+                    _ if idx == side * (side / 2) + side / 2 => b'^',
+                    roll if roll < 5 => b'#',
+                    _ => b'.',
+                })
+                .collect();
+            Guard::new(Grid::new(side, side, cells)).expect("the map has one guard")
+        })
+        .find(|guard| guard.patrol(None) != Patrol::Loops)
+        .expect("some seed lets the guard leave")
+}
+
+fn main() {
+    let side = size_arg(DEFAULT_SIDE);
+    let guard = generate(side);
+
+    let (expected, baseline_time) = time(|| baseline(&guard));
+    let (actual, jump_time) = time(|| guard.find_loop_obstructions());
+    assert_eq!(expected, actual, "implementations disagree");
+
+    println!("{side}x{side} map: {} loop obstructions", actual.len());
+    println!("baseline:   {baseline_time:>12.2?}");
+    println!("jump table: {jump_time:>12.2?}");
+    println!(
+        "speedup:    {:>11.1}x",
+        baseline_time.as_secs_f64() / jump_time.as_secs_f64()
+    );
+}
@@ -0,0 +1,932 @@
+use aoc_common::{
+    trace, Answer, Cords, Grid, ParseError, ParseErrorKind, Position, Result, Solution,
+};
+use std::collections::hash_map::HashMap;
+use std::collections::hash_set::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+pub struct DaySix;
+
+impl Solution for DaySix {
+    type Parsed = Guard;
+
+    const DAY: u8 = 6;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/data.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        Guard::new(Grid::parse(input)?)
+    }
+
+    fn part_one(guard: &Self::Parsed) -> Answer {
+        match guard.patrol(None) {
+            Patrol::Exits(path) => path.len().into(),
+            Patrol::Loops => Answer::Unsolved,
+        }
+    }
+
+    fn part_two(guard: &Self::Parsed) -> Answer {
+        guard
+            .find_loop_obstructions_parallel(default_threads())
+            .len()
+            .into()
+    }
+}
+
+/// One thread per available core, or a single thread if that can't be
+/// determined.
+pub fn default_threads() -> NonZeroUsize {
+    thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The direction a guard drawn as `glyph` faces, if it is one of
+    /// `^ > v <`.
+    pub fn from_glyph(glyph: u8) -> Option<Self> {
+        match glyph {
+            b'^' => Some(Self::Up),
+            b'>' => Some(Self::Right),
+            b'v' => Some(Self::Down),
+            b'<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        }
+    }
+
+    /// Position in [`Direction::ALL`], for tables with one slot per direction.
+    pub fn index(&self) -> usize {
+        self.clone() as usize
+    }
+}
+
+/// How a patrol ends.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Patrol {
+    /// The guard walked off the map, visiting these cells in the order they
+    /// were first reached.
+    Exits(Vec<Cords>),
+    /// The guard came back to a cell facing the same way as before.
+    Loops,
+}
+
+/// For every cell and direction, where a guard walking that way stops: the
+/// cell in front of the next obstacle, or `None` if it walks off the map.
+/// Lets a patrol jump straight from one turn to the next.
+pub struct JumpTable {
+    stops: Grid<[Option<Cords>; 4]>,
+}
+
+impl JumpTable {
+    pub fn new(grid: &Grid<u8>) -> Self {
+        let mut stops = grid.map(|_| [None; 4]);
+        let cells: Vec<Cords> = (0..grid.height())
+            .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+            .collect();
+        let stop = |stops: &Grid<[Option<Cords>; 4]>, pos: Cords, dir: &Direction| match grid
+            .offset(pos, dir.delta())
+        {
+            None => None,
+            Some(ahead) if is_obstructed(&grid[ahead]) => Some(pos),
+            Some(ahead) => stops[ahead][dir.index()],
+        };
+        // Fill each cell after the one in front of it, so every lookup ahead
+        // has already been answered.
+        for &pos in &cells {
+            for dir in [Direction::Up, Direction::Left] {
+                stops[pos][dir.index()] = stop(&stops, pos, &dir);
+            }
+        }
+        for &pos in cells.iter().rev() {
+            for dir in [Direction::Down, Direction::Right] {
+                stops[pos][dir.index()] = stop(&stops, pos, &dir);
+            }
+        }
+        Self { stops }
+    }
+
+    /// Where a guard at `pos` facing `dir` stops, treating `obstruction` as
+    /// blocked if given.
+    pub fn stop(&self, pos: Cords, dir: &Direction, obstruction: Option<Cords>) -> Option<Cords> {
+        let stop = self.stops[pos][dir.index()];
+        let Some(obstruction) = obstruction else {
+            return stop;
+        };
+        match (steps_between(pos, obstruction, dir), stop) {
+            (Some(steps), Some(stop)) if steps > steps_between(pos, stop, dir).unwrap_or(0) => {
+                Some(stop)
+            }
+            (Some(_), _) => {
+                let (dx, dy) = dir.delta();
+                self.stops.offset(obstruction, (-dx, -dy))
+            }
+            (None, stop) => stop,
+        }
+    }
+
+    /// Whether a guard starting at `pos` facing `dir` ends up going round in
+    /// a loop. Only the turns are recorded, one bit per cell and direction,
+    /// since any loop has to turn somewhere.
+    pub fn loops(&self, mut pos: Cords, mut dir: Direction, obstruction: Option<Cords>) -> bool {
+        let width = self.stops.width();
+        let mut turns = vec![0u64; (width * self.stops.height() * 4).div_ceil(64)];
+        while let Some(stop) = self.stop(pos, &dir, obstruction) {
+            pos = stop;
+            dir = dir.turn_right();
+            let bit = (pos.1 * width + pos.0) * 4 + dir.index();
+            let (word, mask) = (bit / 64, 1 << (bit % 64));
+            if turns[word] & mask != 0 {
+                return true;
+            }
+            turns[word] |= mask;
+        }
+        false
+    }
+}
+
+/// How many steps `dir` takes from `from` to reach `to`, if it ever does.
+fn steps_between(from: Cords, to: Cords, dir: &Direction) -> Option<usize> {
+    let ((fx, fy), (tx, ty)) = (from, to);
+    match dir {
+        Direction::Up if fx == tx && ty < fy => Some(fy - ty),
+        Direction::Down if fx == tx && ty > fy => Some(ty - fy),
+        Direction::Left if fy == ty && tx < fx => Some(fx - tx),
+        Direction::Right if fy == ty && tx > fx => Some(tx - fx),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+pub struct Guard {
+    pub grid: Grid<u8>,
+    pub pos: Cords,
+    pub dir: Direction,
+    /// Every cell the guard has stood on, along with the direction it faced.
+    pub patrol_path: HashSet<(Cords, Direction)>,
+    pub loop_locations: Vec<Cords>,
+}
+
+impl Guard {
+    pub fn parse_data(raw_data: Vec<u8>) -> Result<Self> {
+        Self::new(Grid::parse(&raw_data)?)
+    }
+
+    /// Starts the guard wherever the map shows one, facing the way its arrow
+    /// points. The map must hold exactly one guard.
+    pub fn new(grid: Grid<u8>) -> Result<Self> {
+        let mut guards = Self::all(grid)?.into_iter();
+        let guard = guards
+            .next()
+            .expect("`Guard::all` finds at least one guard");
+        if let Some(second) = guards.next() {
+            let (x, y) = second.pos;
+            return Err(ParseError::new(
+                ParseErrorKind::Duplicate(String::from("guard")),
+                Position::new(y + 1, x + 1),
+            ));
+        }
+        Ok(guard)
+    }
+
+    /// Every guard on the map in reading order, each starting out as if it
+    /// were the only one. The map must hold at least one guard.
+    pub fn all(grid: Grid<u8>) -> Result<Vec<Self>> {
+        let starts: Vec<(Cords, Direction)> = grid
+            .iter()
+            .filter_map(|(pos, cell)| Some((pos, Direction::from_glyph(*cell)?)))
+            .collect();
+        if starts.is_empty() {
+            return Err(ParseError::missing("guard"));
+        }
+        Ok(starts
+            .into_iter()
+            .map(|(pos, dir)| {
+                let mut patrol_path = HashSet::new();
+                patrol_path.insert((pos, dir.clone()));
+                Self {
+                    grid: grid.clone(),
+                    dir,
+                    pos,
+                    patrol_path,
+                    loop_locations: Vec::new(),
+                }
+            })
+            .collect())
+    }
+
+    pub fn turn(&mut self) {
+        self.dir = self.dir.turn_right();
+    }
+
+    pub fn next(&self, dir: &Direction, pos: Cords) -> Option<Cords> {
+        self.grid.offset(pos, dir.delta())
+    }
+
+    fn has_visited(&self, pos: Cords) -> bool {
+        Direction::ALL
+            .into_iter()
+            .any(|dir| self.patrol_path.contains(&(pos, dir)))
+    }
+
+    /// Where a guard at `pos` facing `dir` ends up after one step, turning
+    /// right first for as long as it is blocked, or `None` if the step leaves
+    /// the map. A guard blocked on every side just turns on the spot.
+    fn step_from(
+        &self,
+        pos: Cords,
+        dir: &Direction,
+        obstruction: Option<Cords>,
+    ) -> Option<(Cords, Direction)> {
+        let mut dir = dir.clone();
+        for _ in Direction::ALL {
+            let next = self.next(&dir, pos)?;
+            if Some(next) != obstruction && !is_obstructed(&self.grid[next]) {
+                return Some((next, dir));
+            }
+            dir = dir.turn_right();
+        }
+        Some((pos, dir))
+    }
+
+    /// Moves the guard one cell, turning first if it is blocked, and nothing
+    /// else.
+    pub fn step(&mut self) -> Option<Cords> {
+        let (next, dir) = self.step_from(self.pos, &self.dir, None)?;
+        self.pos = next;
+        self.dir = dir;
+        Some(next)
+    }
+
+    /// Moves the guard like [`Guard::step`]. Before stepping onto a cell it
+    /// hasn't visited yet, checks whether blocking that cell instead would
+    /// send the guard into a loop.
+    pub fn next_step(&mut self) -> Option<Cords> {
+        let (next, dir) = self.step_from(self.pos, &self.dir, None)?;
+        trace!("patrol path: {:?}", self.patrol_path);
+        if Direction::from_glyph(self.grid[next]).is_none()
+            && !self.has_visited(next)
+            && self.check_loop(next, dir.turn_right(), self.pos)
+        {
+            self.loop_locations.push(next);
+        }
+        self.patrol_path.insert((next, dir.clone()));
+        self.pos = next;
+        self.dir = dir;
+        Some(next)
+    }
+
+    /// Walks on from `pos` as if `obstruction` were blocked, returning whether
+    /// the guard ends up somewhere it has already been facing the same way.
+    pub fn check_loop(&self, obstruction: Cords, mut dir: Direction, mut pos: Cords) -> bool {
+        let mut loop_path = self.patrol_path.clone();
+
+        while let Some(next) = self.next(&dir, pos) {
+            if next == obstruction || is_obstructed(&self.grid[next]) {
+                dir = dir.turn_right();
+            } else {
+                pos = next;
+            }
+            if !loop_path.insert((pos, dir.clone())) {
+                trace!("crossing paths at {pos:?} facing {dir:?}");
+                return true;
+            }
+        }
+        trace!("no loop: {loop_path:?}");
+        false
+    }
+
+    /// Walks from the guard's current position and direction until it leaves
+    /// the map or loops, treating `obstruction` as blocked if given.
+    pub fn patrol(&self, obstruction: Option<Cords>) -> Patrol {
+        let (mut pos, mut dir) = (self.pos, self.dir.clone());
+        let mut states: HashSet<(Cords, Direction)> = HashSet::new();
+        let mut visited: HashSet<Cords> = HashSet::from([pos]);
+        let mut path = vec![pos];
+
+        while states.insert((pos, dir.clone())) {
+            let Some(next) = self.step_from(pos, &dir, obstruction) else {
+                return Patrol::Exits(path);
+            };
+            (pos, dir) = next;
+            if visited.insert(pos) {
+                path.push(pos);
+            }
+        }
+        Patrol::Loops
+    }
+
+    /// Finds every cell where a new obstruction would trap the guard in a
+    /// loop, by blocking each cell of the original path in turn and walking
+    /// the whole patrol again from the start on a [`JumpTable`]. Cells come
+    /// back in the order the original patrol reaches them.
+    pub fn find_loop_obstructions(&self) -> Vec<Cords> {
+        self.find_loop_obstructions_parallel(NonZeroUsize::MIN)
+    }
+
+    /// [`Guard::find_loop_obstructions`] spread across `threads` scoped
+    /// threads. Each thread claims the next unchecked candidate until none
+    /// are left, and the results are put back in path order so the answer
+    /// doesn't depend on how the work was split.
+    pub fn find_loop_obstructions_parallel(&self, threads: NonZeroUsize) -> Vec<Cords> {
+        let Patrol::Exits(path) = self.patrol(None) else {
+            return Vec::new();
+        };
+        let candidates: Vec<Cords> = path.into_iter().filter(|cell| *cell != self.pos).collect();
+        let table = JumpTable::new(&self.grid);
+        let loops = |cell: Cords| table.loops(self.pos, self.dir.clone(), Some(cell));
+        if threads.get() == 1 {
+            return candidates.into_iter().filter(|cell| loops(*cell)).collect();
+        }
+
+        let next_candidate = AtomicUsize::new(0);
+        let mut found: Vec<(usize, Cords)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads.get().min(candidates.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut found = Vec::new();
+                        loop {
+                            let idx = next_candidate.fetch_add(1, Ordering::Relaxed);
+                            let Some(cell) = candidates.get(idx) else {
+                                return found;
+                            };
+                            if loops(*cell) {
+                                found.push((idx, *cell));
+                            }
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("loop search thread panicked"))
+                .collect()
+        });
+        found.sort_unstable_by_key(|(idx, _)| *idx);
+        found.into_iter().map(|(_, cell)| cell).collect()
+    }
+}
+
+/// Two guards standing on the same cell at the same time.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Collision {
+    pub tick: usize,
+    pub cell: Cords,
+    /// The guards involved, by their place in reading order.
+    pub guards: (usize, usize),
+}
+
+/// What several guards covered when they patrolled the same map together.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Patrols {
+    /// The cells each guard stood on, in the same order as the guards.
+    pub coverage: Vec<HashSet<Cords>>,
+    /// The cells at least one guard stood on.
+    pub combined: HashSet<Cords>,
+    /// The cells more than one guard stood on, not necessarily at once.
+    pub shared: HashSet<Cords>,
+    pub first_collision: Option<Collision>,
+}
+
+impl Patrols {
+    /// Steps every guard on the map with [`Guard::step`] once per tick
+    /// until each has left the map or gone back to a cell facing the same
+    /// way as before. Guards walk through each other, so a looping guard
+    /// keeps walking while others are still going and can still be run into,
+    /// but collisions after every guard has finished are not looked for.
+    pub fn walk(mut guards: Vec<Guard>) -> Self {
+        let mut coverage: Vec<HashSet<Cords>> = guards
+            .iter()
+            .map(|guard| HashSet::from([guard.pos]))
+            .collect();
+        let mut states: Vec<HashSet<(Cords, Direction)>> = guards
+            .iter()
+            .map(|guard| HashSet::from([(guard.pos, guard.dir.clone())]))
+            .collect();
+        let mut looping = vec![false; guards.len()];
+        let mut on_map = vec![true; guards.len()];
+        let mut first_collision = None;
+
+        let mut tick = 0;
+        while (0..guards.len()).any(|idx| on_map[idx] && !looping[idx]) {
+            tick += 1;
+            let mut occupied: HashMap<Cords, usize> = HashMap::new();
+            for (idx, guard) in guards.iter_mut().enumerate() {
+                if !on_map[idx] {
+                    continue;
+                }
+                let Some(cell) = guard.step() else {
+                    on_map[idx] = false;
+                    continue;
+                };
+                looping[idx] |= !states[idx].insert((cell, guard.dir.clone()));
+                coverage[idx].insert(cell);
+                if let Some(other) = occupied.insert(guard.pos, idx) {
+                    trace!("guards {other} and {idx} meet at {:?}", guard.pos);
+                    first_collision.get_or_insert(Collision {
+                        tick,
+                        cell: guard.pos,
+                        guards: (other, idx),
+                    });
+                }
+            }
+        }
+
+        let mut visits: HashMap<Cords, usize> = HashMap::new();
+        for cell in coverage.iter().flatten() {
+            *visits.entry(*cell).or_default() += 1;
+        }
+        Self {
+            combined: visits.keys().copied().collect(),
+            shared: visits
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(cell, _)| cell)
+                .collect(),
+            coverage,
+            first_collision,
+        }
+    }
+}
+
+#[inline]
+fn is_obstructed(pos_value: &u8) -> bool {
+    !(b'.' == *pos_value || Direction::from_glyph(*pos_value).is_some())
+}
+
+#[cfg(test)]
+mod day_six {
+    use super::*;
+    use aoc_common::testing::SeededRng;
+
+    #[test]
+    fn example_one_data_load() {
+        let guard = Guard::parse_data(example_data()).unwrap();
+        assert_eq!(10, guard.grid.width());
+        assert_eq!(10, guard.grid.height());
+    }
+
+    #[test]
+    fn example_one_data_get_byte_last() {
+        let guard = Guard::parse_data(example_data()).unwrap();
+        assert_eq!(Some(&b'.'), guard.grid.get(9, 9));
+    }
+
+    #[test]
+    fn test_start_position() {
+        let guard = Guard::parse_data(example_data()).unwrap();
+        assert_eq!((4, 6), guard.pos);
+        assert_eq!(Some(64), guard.grid.index(guard.pos));
+    }
+
+    #[test]
+    fn test_start_direction() {
+        for (glyph, dir) in [
+            (b"^", Direction::Up),
+            (b">", Direction::Right),
+            (b"v", Direction::Down),
+            (b"<", Direction::Left),
+        ] {
+            let mut data = example_data();
+            data[70] = glyph[0];
+            let guard = Guard::parse_data(data).unwrap();
+            assert_eq!((4, 6), guard.pos);
+            assert_eq!(dir, guard.dir);
+            assert!(!is_obstructed(&glyph[0]));
+        }
+
+        let mut data = example_data();
+        data[70] = b'>';
+        let mut guard = Guard::parse_data(data).unwrap();
+        assert_eq!(Some((5, 6)), guard.next_step());
+    }
+
+    #[test]
+    fn test_guard_count() {
+        let err = Guard::parse_data(b"....\n.#..".to_vec()).err().unwrap();
+        assert_eq!(ParseErrorKind::Missing(String::from("guard")), err.kind);
+        assert_eq!("no guard found", err.to_string());
+
+        let err = Guard::parse_data(b"..^.\n.#<.".to_vec()).err().unwrap();
+        assert_eq!(ParseErrorKind::Duplicate(String::from("guard")), err.kind);
+        assert_eq!("line 2, column 3: more than one guard", err.to_string());
+    }
+
+    #[test]
+    fn test_step() {
+        let mut guard = Guard::parse_data(example_data()).unwrap();
+        guard.pos = (4, 1);
+        assert_eq!(Some((5, 1)), guard.step());
+        assert_eq!(Direction::Right, guard.dir);
+        assert_eq!(1, guard.patrol_path.len());
+        assert!(guard.loop_locations.is_empty());
+
+        let mut boxed = Guard::parse_data(b".#.\n#^#\n.#.".to_vec()).unwrap();
+        assert_eq!(Some((1, 1)), boxed.step());
+        assert_eq!(Some((1, 1)), boxed.next_step());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut guard = Guard::parse_data(example_data()).unwrap();
+        let next = guard.next_step();
+        assert!(next.is_some());
+        assert_eq!((4, 5), next.expect("Previously Asserted"));
+    }
+
+    #[test]
+    fn test_next_turn() {
+        let mut guard = Guard::parse_data(example_data()).unwrap();
+        guard.pos = (4, 1);
+        let next = guard.next_step();
+        assert!(next.is_some());
+        assert_eq!((5, 1), next.expect("Previously Asserted"));
+        guard.pos = (8, 1);
+        let next = guard.next_step();
+        assert!(next.is_some());
+        assert_eq!((8, 2), next.expect("Previously Asserted"));
+    }
+
+    #[test]
+    fn test_walk_path() {
+        let mut guard = Guard::parse_data(example_data()).unwrap();
+        let mut locations: HashSet<Cords> = HashSet::new();
+
+        while let Some(location) = guard.next_step() {
+            let _ = locations.insert(location);
+        }
+        assert_eq!(41, locations.len());
+    }
+
+    #[test]
+    fn test_loop_locations() {
+        let mut guard = Guard::parse_data(example_data()).unwrap();
+        let mut locations: HashSet<Cords> = HashSet::new();
+
+        while let Some(location) = guard.next_step() {
+            let _ = locations.insert(location);
+        }
+        println!("{0:?}", guard.loop_locations);
+        for location in &guard.loop_locations {
+            guard.grid[*location] = b'O';
+        }
+        println!("{}", guard.grid);
+
+        assert_eq!(6, guard.loop_locations.len());
+        assert!(guard.loop_locations.contains(&(3, 6)));
+        assert!(guard.loop_locations.contains(&(6, 7)));
+        assert!(guard.loop_locations.contains(&(7, 7)));
+        assert!(guard.loop_locations.contains(&(1, 8)));
+        assert!(guard.loop_locations.contains(&(3, 8)));
+        assert!(guard.loop_locations.contains(&(7, 9)));
+    }
+
+    #[test]
+    fn test_obstructed_loop() {
+        let mut guard = Guard::parse_data(example_data_obstructed_loop()).unwrap();
+        let mut locations: HashSet<Cords> = HashSet::new();
+
+        while let Some(location) = guard.next_step() {
+            let _ = locations.insert(location);
+        }
+        println!("{0:?}", guard.loop_locations);
+        for location in &guard.loop_locations {
+            guard.grid[*location] = b'O';
+        }
+        println!("{}", guard.grid);
+
+        assert_eq!(7, guard.loop_locations.len());
+
+        assert!(guard.loop_locations.contains(&(3, 6)));
+        assert!(guard.loop_locations.contains(&(6, 7)));
+        assert!(guard.loop_locations.contains(&(7, 7)));
+        assert!(guard.loop_locations.contains(&(1, 8)));
+        assert!(guard.loop_locations.contains(&(3, 8)));
+        assert!(guard.loop_locations.contains(&(7, 9)));
+
+        assert!(guard.loop_locations.contains(&(4, 8)));
+    }
+
+    #[test]
+    fn test_reddit_debug_map() {
+        let mut guard = Guard::parse_data(debug_data()).unwrap();
+        let mut locations: HashSet<Cords> = HashSet::new();
+
+        while let Some(location) = guard.next_step() {
+            let _ = locations.insert(location);
+        }
+
+        println!("{0:?}", guard.loop_locations);
+        for location in &guard.loop_locations {
+            guard.grid[*location] = b'O';
+        }
+        println!("{}", guard.grid);
+
+        assert_eq!(19, guard.loop_locations.len());
+        let guard = Guard::parse_data(debug_data()).unwrap();
+        assert_eq!(19, guard.find_loop_obstructions().len());
+    }
+
+    #[test]
+    fn test_solution_part_one() {
+        let guard = DaySix::parse(&example_data()).unwrap();
+        assert_eq!(Answer::from(41), DaySix::part_one(&guard));
+
+        // The guard never walks back over its start, which still counts.
+        let guard = DaySix::parse(b"...\n.^.\n...").unwrap();
+        assert_eq!(Answer::from(2), DaySix::part_one(&guard));
+
+        let looping = DaySix::parse(b".#..\n...#\n#^..\n..#.").unwrap();
+        assert_eq!(Answer::Unsolved, DaySix::part_one(&looping));
+        let boxed = DaySix::parse(b".#.\n#^#\n.#.").unwrap();
+        assert_eq!(Answer::Unsolved, DaySix::part_one(&boxed));
+    }
+
+    fn example_data() -> Vec<u8> {
+        b"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."
+            .into()
+    }
+
+    #[test]
+    fn test_find_loop_obstructions() {
+        let guard = Guard::parse_data(example_data()).unwrap();
+        assert_eq!(
+            vec![(3, 6), (6, 7), (3, 8), (1, 8), (7, 7), (7, 9)],
+            guard.find_loop_obstructions()
+        );
+        assert_eq!(
+            Answer::from(6),
+            DaySix::part_two(&DaySix::parse(&example_data()).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_patrol() {
+        let guard = Guard::parse_data(example_data()).unwrap();
+        let Patrol::Exits(path) = guard.patrol(None) else {
+            panic!("the example patrol leaves the map");
+        };
+        assert_eq!(41, path.len());
+        assert_eq!(Some(&(4, 6)), path.first());
+        assert_eq!(Some(&(7, 9)), path.last());
+        assert_eq!(Patrol::Loops, guard.patrol(Some((3, 6))));
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let mut rng = SeededRng::new(0x2024_1222);
+        let grids = [
+            Grid::parse(&debug_data()).unwrap(),
+            Grid::parse(&example_data_obstructed_loop()).unwrap(),
+        ]
+        .into_iter()
+        .chain((0..50).map(|_| random_grid(&mut rng)));
+
+        for grid in grids {
+            let guard = Guard::new(grid).unwrap();
+            let serial = guard.find_loop_obstructions();
+            for threads in 1..=4 {
+                let threads = NonZeroUsize::new(threads).unwrap();
+                assert_eq!(
+                    serial,
+                    guard.find_loop_obstructions_parallel(threads),
+                    "{threads} threads\n{}",
+                    guard.grid
+                );
+            }
+        }
+    }
+
+    /// A small random map with a guard somewhere in it, from a seeded
+    /// generator so failures can be reproduced.
+    fn random_grid(rng: &mut SeededRng) -> Grid<u8> {
+        let mut random = |modulus: u64| rng.below(modulus);
+        let (width, height) = (3 + random(10) as usize, 3 + random(10) as usize);
+        let density = 5 + random(20);
+        let mut cells: Vec<u8> = (0..width * height)
+            .map(|_| if random(100) < density { b'#' } else { b'.' })
+            .collect();
+        let start = random(cells.len() as u64) as usize;
+        cells[start] = b"^>v<"[random(4) as usize];
+        Grid::new(width, height, cells)
+    }
+
+    #[test]
+    fn test_incremental_matches_brute_force() {
+        let mut rng = SeededRng::new(0x2024_1206);
+        for _ in 0..300 {
+            let grid = random_grid(&mut rng);
+            let mut guard = Guard::new(grid.clone()).unwrap();
+            if guard.patrol(None) == Patrol::Loops {
+                // Stepping would never finish, and there is nothing to block.
+                assert!(guard.find_loop_obstructions().is_empty());
+                continue;
+            }
+            while guard.next_step().is_some() {}
+            let mut incremental = guard.loop_locations;
+            let mut brute_force = Guard::new(grid.clone()).unwrap().find_loop_obstructions();
+            incremental.sort();
+            brute_force.sort();
+            assert_eq!(brute_force, incremental, "\n{grid}");
+        }
+    }
+
+    #[test]
+    fn test_jump_table() {
+        let grid = Grid::parse(&example_data()).unwrap();
+        let table = JumpTable::new(&grid);
+        assert_eq!(Some((4, 1)), table.stop((4, 6), &Direction::Up, None));
+        assert_eq!(Some((8, 1)), table.stop((4, 1), &Direction::Right, None));
+        assert_eq!(None, table.stop((7, 7), &Direction::Down, None));
+        assert_eq!(
+            Some((4, 5)),
+            table.stop((4, 6), &Direction::Up, Some((4, 4)))
+        );
+        assert_eq!(
+            Some((4, 6)),
+            table.stop((4, 6), &Direction::Up, Some((4, 5)))
+        );
+        assert_eq!(
+            Some((4, 1)),
+            table.stop((4, 6), &Direction::Up, Some((4, 0)))
+        );
+        assert_eq!(
+            Some((7, 8)),
+            table.stop((7, 7), &Direction::Down, Some((7, 9)))
+        );
+        assert!(!table.loops((4, 6), Direction::Up, None));
+        assert!(table.loops((4, 6), Direction::Up, Some((3, 6))));
+    }
+
+    #[test]
+    fn test_jump_table_matches_patrol() {
+        let mut rng = SeededRng::new(0x2024_1223);
+        for _ in 0..200 {
+            let grid = random_grid(&mut rng);
+            let guard = Guard::new(grid.clone()).unwrap();
+            let table = JumpTable::new(&grid);
+            let obstructions = grid
+                .iter()
+                .filter(|(pos, cell)| **cell == b'.' && *pos != guard.pos)
+                .map(|(pos, _)| Some(pos))
+                .chain([None]);
+            for obstruction in obstructions {
+                assert_eq!(
+                    guard.patrol(obstruction) == Patrol::Loops,
+                    table.loops(guard.pos, guard.dir.clone(), obstruction),
+                    "obstruction at {obstruction:?}\n{grid}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_guard_all() {
+        let guards = Guard::all(Grid::parse(b"..^.\n.#<.").unwrap()).unwrap();
+        let starts: Vec<_> = guards
+            .iter()
+            .map(|guard| (guard.pos, guard.dir.clone()))
+            .collect();
+        assert_eq!(
+            vec![((2, 0), Direction::Up), ((2, 1), Direction::Left)],
+            starts
+        );
+        assert!(Guard::all(Grid::parse(b"....").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_patrols_collide() {
+        let guards = Guard::all(Grid::parse(b"..>...<..").unwrap()).unwrap();
+        let patrols = Patrols::walk(guards);
+        assert_eq!(
+            vec![7, 7],
+            patrols
+                .coverage
+                .iter()
+                .map(HashSet::len)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(9, patrols.combined.len());
+        assert_eq!(
+            HashSet::from([(2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]),
+            patrols.shared
+        );
+        assert_eq!(
+            Some(Collision {
+                tick: 2,
+                cell: (4, 0),
+                guards: (0, 1),
+            }),
+            patrols.first_collision
+        );
+
+        // Guards swapping places pass each other without sharing a cell.
+        let guards = Guard::all(Grid::parse(b"..><..").unwrap()).unwrap();
+        assert_eq!(None, Patrols::walk(guards).first_collision);
+    }
+
+    #[test]
+    fn test_patrols_with_looping_guard() {
+        let data = b".#...
+....#
+....>
+#^...
+...#.";
+        let patrols = Patrols::walk(Guard::all(Grid::parse(data).unwrap()).unwrap());
+        assert_eq!(
+            vec![1, 8],
+            patrols
+                .coverage
+                .iter()
+                .map(HashSet::len)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(9, patrols.combined.len());
+        assert!(patrols.shared.is_empty());
+        assert_eq!(None, patrols.first_collision);
+
+        let boxed = Guard::all(Grid::parse(b".#.\n#^#\n.#>").unwrap()).unwrap();
+        let patrols = Patrols::walk(boxed);
+        assert_eq!(HashSet::from([(1, 1)]), patrols.coverage[0]);
+    }
+
+    #[test]
+    fn test_patrols_single_guard_matches_patrol() {
+        let mut rng = SeededRng::new(0x2024_1225);
+        for _ in 0..100 {
+            let grid = random_grid(&mut rng);
+            let guard = Guard::new(grid.clone()).unwrap();
+            let Patrol::Exits(path) = guard.patrol(None) else {
+                continue;
+            };
+            let patrols = Patrols::walk(vec![guard]);
+            assert_eq!(HashSet::from_iter(path), patrols.combined, "\n{grid}");
+            assert!(patrols.shared.is_empty());
+        }
+    }
+
+    fn example_data_obstructed_loop() -> Vec<u8> {
+        b"....##....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."
+            .into()
+    }
+
+    fn debug_data() -> Vec<u8> {
+        b"...........#.....#......
+...................#....
+...#.....##.............
+......................#.
+..................#.....
+..#.....................
+....................#...
+........................
+.#........^.............
+..........#..........#..
+..#.....#..........#....
+........#.....#..#......"
+            .into()
+    }
+}
@@ -0,0 +1,319 @@
+use aoc_common::{Answer, ByteCursor, Result, Solution};
+use std::ops::{Deref, DerefMut};
+
+pub struct DayThree;
+
+impl Solution for DayThree {
+    type Parsed = Vec<Instruction>;
+
+    const DAY: u8 = 3;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/data.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        Ok(DataParser::new(input.to_vec()).collect())
+    }
+
+    fn part_one(instructions: &Self::Parsed) -> Answer {
+        sum_products(instructions).into()
+    }
+
+    fn part_two(instructions: &Self::Parsed) -> Answer {
+        sum_products(enabled(instructions)).into()
+    }
+}
+
+/// The keywords the scanner looks for, in the order they are tried.
+const KEYWORDS: [&[u8]; 3] = [b"mul(", b"do()", b"don't()"];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+pub fn sum_products<'a, I: IntoIterator<Item = &'a Instruction>>(instructions: I) -> u64 {
+    instructions
+        .into_iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(a, b) => u64::from(*a) * u64::from(*b),
+            Instruction::Do | Instruction::Dont => 0,
+        })
+        .sum()
+}
+
+/// Yields the `mul` instructions that are switched on, starting enabled and
+/// toggling at each `do()` / `don't()`.
+pub fn enabled(instructions: &[Instruction]) -> impl Iterator<Item = &Instruction> {
+    instructions
+        .iter()
+        .scan(true, |enabled, instruction| {
+            match instruction {
+                Instruction::Do => *enabled = true,
+                Instruction::Dont => *enabled = false,
+                Instruction::Mul(..) => {}
+            }
+            Some((*enabled, instruction))
+        })
+        .filter_map(|(enabled, instruction)| {
+            (enabled && matches!(instruction, Instruction::Mul(..))).then_some(instruction)
+        })
+}
+
+pub struct DataParser {
+    data: ByteCursor,
+}
+
+impl Deref for DataParser {
+    type Target = ByteCursor;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl DerefMut for DataParser {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl DataParser {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self {
+            data: ByteCursor::new(data.into_boxed_slice()),
+        }
+    }
+
+    /// Skips forward to the next byte that could start one of [`KEYWORDS`].
+    fn skip_invalid_chars(&mut self) {
+        self.data
+            .skip_while(|b| KEYWORDS.iter().all(|keyword| keyword[0] != b));
+    }
+
+    /// Reads a 1-3 digit operand, leaving the cursor in place if there isn't
+    /// one.
+    fn read_operand(&mut self) -> Option<u32> {
+        let checkpoint = self.checkpoint();
+        let start = self.cursor;
+        match self.read_unsigned() {
+            Some(val) if (1..=3).contains(&(self.cursor - start)) => Some(val as u32),
+            _ => {
+                self.rewind(checkpoint);
+                None
+            }
+        }
+    }
+
+    /// Reads a complete `mul(X,Y)`, rewinding if anything about it is
+    /// malformed.
+    fn read_mul(&mut self) -> Option<Instruction> {
+        let checkpoint = self.checkpoint();
+        let instruction = self.read_mul_unchecked();
+        if instruction.is_none() {
+            self.rewind(checkpoint);
+        }
+        instruction
+    }
+
+    fn read_mul_unchecked(&mut self) -> Option<Instruction> {
+        if !self.expect_literal(KEYWORDS[0]) {
+            return None;
+        }
+        let a = self.read_operand()?;
+        if !self.expect_literal(b",") {
+            return None;
+        }
+        let b = self.read_operand()?;
+        if !self.expect_literal(b")") {
+            return None;
+        }
+        Some(Instruction::Mul(a, b))
+    }
+
+    pub fn next_instruction(&mut self) -> Option<Instruction> {
+        loop {
+            self.skip_invalid_chars();
+            if self.eof() {
+                return None;
+            }
+            if let Some(instruction) = self.read_mul() {
+                return Some(instruction);
+            }
+            if self.expect_literal(KEYWORDS[1]) {
+                return Some(Instruction::Do);
+            }
+            if self.expect_literal(KEYWORDS[2]) {
+                return Some(Instruction::Dont);
+            }
+            let _ = self.read_byte();
+        }
+    }
+}
+
+impl Iterator for DataParser {
+    type Item = Instruction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_instruction()
+    }
+}
+
+#[cfg(test)]
+mod day_three {
+    use crate::{enabled, sum_products, DataParser, DayThree, Instruction};
+    use aoc_common::{Answer, Solution};
+
+    fn example_data() -> Vec<u8> {
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5)"
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn example_two_data() -> Vec<u8> {
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
+            .as_bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn data_parser_peek() {
+        let data = example_data();
+        let report_data = DataParser::new(data);
+        let peek = report_data.peek();
+        assert!(peek.is_some());
+        assert_eq!(b'x', *peek.unwrap());
+        assert_eq!(0, report_data.cursor);
+    }
+
+    #[test]
+    fn data_parser_read_byte() {
+        let data = example_data();
+        let mut report_data = DataParser::new(data);
+        let byte = report_data.read_byte();
+
+        assert!(byte.is_some());
+        assert_eq!(b'x', byte.unwrap());
+        assert_eq!(1, report_data.cursor);
+    }
+
+    #[test]
+    fn data_parser_skip_invalid_chars() {
+        let data = example_data();
+        let mut report_data = DataParser::new(data);
+        report_data.skip_invalid_chars();
+        assert_eq!(1, report_data.cursor);
+        assert_eq!(b'm', *report_data.peek().unwrap());
+
+        report_data.skip_invalid_chars();
+        assert_eq!(1, report_data.cursor);
+        assert_eq!(b'm', *report_data.peek().unwrap());
+
+        report_data.cursor = 9;
+        report_data.skip_invalid_chars();
+        assert_eq!(11, report_data.cursor);
+        assert_eq!(b'm', *report_data.peek().unwrap());
+    }
+
+    #[test]
+    fn data_parser_read_mul() {
+        let mut parser = DataParser::new(b"mul(2,4)".to_vec());
+        assert_eq!(Some(Instruction::Mul(2, 4)), parser.read_mul());
+        assert!(parser.eof());
+    }
+
+    #[test]
+    fn data_parser_rejects_malformed() {
+        for data in [
+            "mul[3,7]",
+            "mul(32,64]",
+            "mul(4*",
+            "mul ( 2 , 4 )",
+            "mul(1234,5)",
+            "mul(,5)",
+            "mul(2,4",
+        ] {
+            let mut parser = DataParser::new(data.as_bytes().to_vec());
+            assert_eq!(None, parser.read_mul(), "{data}");
+            assert_eq!(0, parser.cursor, "{data}");
+        }
+    }
+
+    #[test]
+    fn data_parser_instructions() {
+        let instructions: Vec<Instruction> = DataParser::new(example_data()).collect();
+        assert_eq!(
+            vec![
+                Instruction::Mul(2, 4),
+                Instruction::Mul(5, 5),
+                Instruction::Mul(11, 8),
+                Instruction::Mul(8, 5),
+            ],
+            instructions
+        );
+        assert_eq!(161, sum_products(&instructions));
+    }
+
+    #[test]
+    fn data_parser_recovers_after_partial_match() {
+        let instructions: Vec<Instruction> =
+            DataParser::new(b"mulmul(3,3)mul(1,mul(2,2)".to_vec()).collect();
+        assert_eq!(
+            vec![Instruction::Mul(3, 3), Instruction::Mul(2, 2)],
+            instructions
+        );
+    }
+
+    #[test]
+    fn data_parser_skip_invalid_chars_to_keyword() {
+        let mut parser = DataParser::new(b"!@don't()".to_vec());
+        parser.skip_invalid_chars();
+        assert_eq!(2, parser.cursor);
+    }
+
+    #[test]
+    fn data_parser_conditionals() {
+        let instructions: Vec<Instruction> = DataParser::new(example_two_data()).collect();
+        assert_eq!(
+            vec![
+                Instruction::Mul(2, 4),
+                Instruction::Dont,
+                Instruction::Mul(5, 5),
+                Instruction::Mul(11, 8),
+                Instruction::Do,
+                Instruction::Mul(8, 5),
+            ],
+            instructions
+        );
+    }
+
+    #[test]
+    fn data_parser_rejects_malformed_conditionals() {
+        let instructions: Vec<Instruction> =
+            DataParser::new(b"do_not()don't(do( )dont()".to_vec()).collect();
+        assert!(instructions.is_empty());
+    }
+
+    #[test]
+    fn enabled_products() {
+        let instructions: Vec<Instruction> = DataParser::new(example_two_data()).collect();
+        assert_eq!(
+            vec![&Instruction::Mul(2, 4), &Instruction::Mul(8, 5)],
+            enabled(&instructions).collect::<Vec<_>>()
+        );
+        assert_eq!(48, sum_products(enabled(&instructions)));
+    }
+
+    #[test]
+    fn solution_part_two() {
+        let instructions = DayThree::parse(&example_two_data()).unwrap();
+        assert_eq!(Answer::from(161), DayThree::part_one(&instructions));
+        assert_eq!(Answer::from(48), DayThree::part_two(&instructions));
+    }
+
+    #[test]
+    fn solution_part_one() {
+        let instructions = DayThree::parse(&example_data()).unwrap();
+        assert_eq!(Answer::from(161), DayThree::part_one(&instructions));
+    }
+}
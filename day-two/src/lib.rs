@@ -0,0 +1,1046 @@
+use aoc_common::{debug, trace, Answer, ByteCursor, ParseError, ParseErrorKind, Result, Solution};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+pub struct DayTwo;
+
+/// A single reading within a report.
+pub type Level = i64;
+
+impl Solution for DayTwo {
+    type Parsed = Vec<Vec<Level>>;
+
+    const DAY: u8 = 2;
+    const INPUT: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input/reports.txt");
+
+    fn parse(input: &[u8]) -> Result<Self::Parsed> {
+        ReportReader::new(input).collect()
+    }
+
+    fn part_one(reports: &Self::Parsed) -> Answer {
+        reports
+            .iter()
+            .filter(|report| is_strictly_safe(report))
+            .count()
+            .into()
+    }
+
+    fn part_two(reports: &Self::Parsed) -> Answer {
+        reports
+            .iter()
+            .filter(|report| is_report_safe(report).is_safe())
+            .count()
+            .into()
+    }
+}
+
+/// Which way the levels of a report have to move.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+    /// Either all increasing or all decreasing.
+    Either,
+    /// The direction may change from step to step.
+    Any,
+}
+
+impl FromStr for Monotonicity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "increasing" => Ok(Self::Increasing),
+            "decreasing" => Ok(Self::Decreasing),
+            "either" => Ok(Self::Either),
+            "any" => Ok(Self::Any),
+            _ => Err(format!(
+                "unknown monotonicity `{s}`, expected increasing, decreasing, either or any"
+            )),
+        }
+    }
+}
+
+/// The rules a report has to follow to be considered safe.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed difference between adjacent levels.
+    pub min_step: u64,
+    /// Largest allowed difference between adjacent levels.
+    pub max_step: u64,
+    /// How many levels the Problem Dampener may remove.
+    pub allowed_faults: usize,
+    /// Whether equal adjacent levels are allowed.
+    pub allow_plateaus: bool,
+    pub monotonicity: Monotonicity,
+}
+
+impl Default for SafetyPolicy {
+    /// The puzzle's rules, with the Problem Dampener switched on.
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            allowed_faults: 1,
+            allow_plateaus: false,
+            monotonicity: Monotonicity::Either,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// The puzzle's rules without the Problem Dampener.
+    pub fn strict() -> Self {
+        Self {
+            allowed_faults: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Whether stepping from `a` to `b` is allowed while the report is heading
+    /// in the given direction, `None` meaning any direction.
+    #[inline]
+    fn is_safe_step(&self, a: Level, b: Level, accending: Option<bool>) -> bool {
+        if a == b {
+            return self.allow_plateaus;
+        }
+        if accending.is_some_and(|accending| (b > a) != accending) {
+            return false;
+        }
+        (self.min_step..=self.max_step).contains(&a.abs_diff(b))
+    }
+
+    fn directions(&self) -> &'static [Option<bool>] {
+        match self.monotonicity {
+            Monotonicity::Increasing => &[Some(true)],
+            Monotonicity::Decreasing => &[Some(false)],
+            Monotonicity::Either => &[Some(true), Some(false)],
+            Monotonicity::Any => &[None],
+        }
+    }
+
+    /// Finds the fewest levels to remove so the rest of `report` follows the
+    /// rules in the given direction. Returns the removed indices, or `None`
+    /// if that takes more than `allowed_faults` removals.
+    ///
+    /// `cost[i]` is the fewest removals before `i` that leave a valid run
+    /// ending at `i`. Only the `allowed_faults + 1` levels before `i` can
+    /// precede it, which keeps this linear for a fixed policy.
+    fn removals(&self, report: &[Level], accending: Option<bool>) -> Option<Vec<usize>> {
+        // No report needs more removals than it has levels, and clamping keeps
+        // `faults + 1` from overflowing for a policy allowing `usize::MAX`.
+        let faults = self.allowed_faults.min(report.len());
+        let mut cost = vec![usize::MAX; report.len()];
+        let mut parent: Vec<Option<usize>> = vec![None; report.len()];
+
+        for idx in 0..report.len() {
+            for prev in (idx.saturating_sub(faults + 1)..idx).rev() {
+                if cost[prev] == usize::MAX
+                    || !self.is_safe_step(report[prev], report[idx], accending)
+                {
+                    continue;
+                }
+                let removed = cost[prev] + idx - prev - 1;
+                if removed < cost[idx] {
+                    cost[idx] = removed;
+                    parent[idx] = Some(prev);
+                }
+            }
+            if idx <= faults && idx < cost[idx] {
+                cost[idx] = idx;
+                parent[idx] = None;
+            }
+        }
+
+        let mut best: Option<(usize, usize)> = None;
+        for (idx, removed) in cost.iter().enumerate().rev() {
+            if *removed == usize::MAX {
+                continue;
+            }
+            let removed = removed + report.len() - 1 - idx;
+            if best.is_none_or(|(_, best_removed)| removed < best_removed) {
+                best = Some((idx, removed));
+            }
+        }
+
+        let Some((last, removed)) = best else {
+            return Some(Vec::new());
+        };
+        if removed > faults {
+            return None;
+        }
+        let mut kept = vec![false; report.len()];
+        let mut idx = Some(last);
+        while let Some(current) = idx {
+            kept[current] = true;
+            idx = parent[current];
+        }
+        Some((0..report.len()).filter(|idx| !kept[*idx]).collect())
+    }
+}
+
+/// A report is safe when its levels only increase or only decrease, by 1 to 3
+/// at each step.
+pub fn is_strictly_safe(report: &[Level]) -> bool {
+    first_violation(report, &SafetyPolicy::strict()).is_none()
+}
+
+/// The Problem Dampener by definition: the report is safe if it follows the
+/// rules after removing up to `allowed_faults` levels, trying every choice.
+pub fn is_report_safe_brute_force(report: &[Level], policy: &SafetyPolicy) -> bool {
+    first_violation(report, policy).is_none()
+        || (policy.allowed_faults > 0
+            && (0..report.len()).any(|skip| {
+                let mut report = report.to_vec();
+                report.remove(skip);
+                let policy = SafetyPolicy {
+                    allowed_faults: policy.allowed_faults - 1,
+                    ..*policy
+                };
+                is_report_safe_brute_force(&report, &policy)
+            }))
+}
+
+/// Why a report failed the safety check.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum UnsafeReason {
+    /// Two adjacent levels differ by less than the policy's `min_step` or
+    /// more than its `max_step`.
+    Gap,
+    /// Two adjacent levels are equal and the policy doesn't allow plateaus.
+    Zero,
+    /// The levels switch between increasing and decreasing.
+    DirectionChange,
+}
+
+impl fmt::Display for UnsafeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gap => "gap",
+            Self::Zero => "zero",
+            Self::DirectionChange => "direction change",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SafetyVerdict {
+    Safe,
+    /// Safe once some levels are removed, `index` being the first of them.
+    SafeWithRemoval {
+        index: usize,
+    },
+    /// Unsafe even with the Problem Dampener. `first_violation_index` is the
+    /// level at which the unmodified report first breaks the rules.
+    Unsafe {
+        first_violation_index: usize,
+        reason: UnsafeReason,
+    },
+}
+
+impl SafetyVerdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Self::Unsafe { .. })
+    }
+}
+
+/// Finds the first level that breaks the rules. When either direction is
+/// allowed it is taken from the first step that changes level.
+pub fn first_violation(report: &[Level], policy: &SafetyPolicy) -> Option<(usize, UnsafeReason)> {
+    let accending = match policy.monotonicity {
+        Monotonicity::Increasing => Some(true),
+        Monotonicity::Decreasing => Some(false),
+        Monotonicity::Either => report
+            .windows(2)
+            .find(|pair| pair[0] != pair[1])
+            .map(|pair| pair[1] > pair[0]),
+        Monotonicity::Any => None,
+    };
+    report.windows(2).enumerate().find_map(|(idx, pair)| {
+        let reason = if pair[0] == pair[1] {
+            if policy.allow_plateaus {
+                return None;
+            }
+            UnsafeReason::Zero
+        } else if accending.is_some_and(|accending| (pair[1] > pair[0]) != accending) {
+            UnsafeReason::DirectionChange
+        } else if !(policy.min_step..=policy.max_step).contains(&pair[0].abs_diff(pair[1])) {
+            UnsafeReason::Gap
+        } else {
+            return None;
+        };
+        Some((idx + 1, reason))
+    })
+}
+
+/// Checks `report` against the puzzle's rules with the Problem Dampener.
+pub fn is_report_safe(report: &[Level]) -> SafetyVerdict {
+    check_report(report, &SafetyPolicy::default())
+}
+
+/// Checks `report` against `policy`. The removals needed in each allowed
+/// direction are found in a single pass, so this stays linear in the report
+/// length for a fixed number of allowed faults.
+pub fn check_report(report: &[Level], policy: &SafetyPolicy) -> SafetyVerdict {
+    let mut verdict = SafetyVerdict::Safe;
+
+    if let Some((first_violation_index, reason)) = first_violation(report, policy) {
+        verdict = SafetyVerdict::Unsafe {
+            first_violation_index,
+            reason,
+        };
+        let mut fewest: Option<Vec<usize>> = None;
+        for accending in policy.directions() {
+            let removals = policy.removals(report, *accending);
+            trace!("{report:?} accending: {accending:?} | removals: {removals:?}");
+            if let Some(removals) = removals {
+                if fewest
+                    .as_ref()
+                    .is_none_or(|fewest| removals.len() < fewest.len())
+                {
+                    fewest = Some(removals);
+                }
+            }
+        }
+        if let Some(removals) = fewest {
+            verdict = removals.first().map_or(SafetyVerdict::Safe, |index| {
+                SafetyVerdict::SafeWithRemoval { index: *index }
+            });
+        }
+    }
+    debug!("check_report({report:?}, {policy:?}) -> {verdict:?}");
+    verdict
+}
+
+/// Tallies verdicts by outcome. Only a summary made with
+/// [`VerdictSummary::with_lines`] also remembers the line of every unsafe
+/// report, so the default one stays the same size however long the input.
+#[derive(Debug, Default)]
+pub struct VerdictSummary {
+    pub safe: usize,
+    pub safe_with_removal: usize,
+    pub unsafe_counts: BTreeMap<UnsafeReason, usize>,
+    pub unsafe_lines: Option<BTreeMap<UnsafeReason, Vec<usize>>>,
+}
+
+impl VerdictSummary {
+    pub fn with_lines() -> Self {
+        Self {
+            unsafe_lines: Some(BTreeMap::new()),
+            ..Self::default()
+        }
+    }
+
+    pub fn add(&mut self, line: usize, verdict: SafetyVerdict) {
+        match verdict {
+            SafetyVerdict::Safe => self.safe += 1,
+            SafetyVerdict::SafeWithRemoval { .. } => self.safe_with_removal += 1,
+            SafetyVerdict::Unsafe { reason, .. } => {
+                *self.unsafe_counts.entry(reason).or_default() += 1;
+                if let Some(unsafe_lines) = &mut self.unsafe_lines {
+                    unsafe_lines.entry(reason).or_default().push(line);
+                }
+            }
+        }
+    }
+
+    pub fn unsafe_count(&self) -> usize {
+        self.unsafe_counts.values().sum()
+    }
+}
+
+impl fmt::Display for VerdictSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<28}{:>8}", "Verdict", "Reports")?;
+        writeln!(f, "{:<28}{:>8}", "safe", self.safe)?;
+        writeln!(
+            f,
+            "{:<28}{:>8}",
+            "safe with removal", self.safe_with_removal
+        )?;
+        for reason in [
+            UnsafeReason::Gap,
+            UnsafeReason::Zero,
+            UnsafeReason::DirectionChange,
+        ] {
+            let count = self.unsafe_counts.get(&reason).copied().unwrap_or(0);
+            writeln!(f, "{:<28}{count:>8}", format!("unsafe: {reason}"))?;
+        }
+        for (reason, lines) in self.unsafe_lines.iter().flatten() {
+            let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+            write!(f, "\nUnsafe ({reason}) on lines: {}", lines.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ReportData<T: AsRef<[u8]> = Vec<u8>> {
+    data: ByteCursor<T>,
+}
+
+impl<T: AsRef<[u8]>> Deref for ReportData<T> {
+    type Target = ByteCursor<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T: AsRef<[u8]>> DerefMut for ReportData<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T: AsRef<[u8]>> ReportData<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data: ByteCursor::new(data),
+        }
+    }
+
+    fn skip_space(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
+    }
+
+    /// Whether the cursor sits on a `\n` or `\r\n` line ending, or a `\r`
+    /// right before the end of the data.
+    fn at_line_end(&self) -> bool {
+        match self.peek() {
+            Some(b'\n') => true,
+            Some(b'\r') => matches!(self.data().get(self.cursor + 1), None | Some(b'\n')),
+            _ => false,
+        }
+    }
+
+    /// Reads the next level on the current line, returning `None` once the
+    /// line is used up. Anything that isn't a whole signed number that fits
+    /// in a [`Level`] is reported at the start of the token.
+    fn read_value(&mut self) -> Option<Result<Level>> {
+        self.skip_space();
+        if self.at_token_end() {
+            return None;
+        }
+
+        let checkpoint = self.checkpoint();
+        if let Some(value) = self.read_signed().filter(|_| self.at_token_end()) {
+            return Some(Ok(value));
+        }
+
+        self.rewind(checkpoint);
+        let start = self.cursor;
+        while !self.at_token_end() {
+            self.cursor += 1;
+        }
+        let token = String::from_utf8_lossy(&self.data()[start..self.cursor]).into_owned();
+        self.rewind(checkpoint);
+        Some(Err(self.error(ParseErrorKind::MalformedNumber(token))))
+    }
+
+    fn at_token_end(&self) -> bool {
+        self.eof() || self.at_line_end() || matches!(self.peek(), Some(b' ' | b'\t'))
+    }
+
+    /// Reads the next report, skipping blank lines rather than returning them
+    /// as empty reports.
+    pub fn read_report(&mut self) -> Option<Result<Vec<Level>>> {
+        loop {
+            let mut report: Vec<Level> = Vec::new();
+
+            while let Some(val) = self.read_value() {
+                match val {
+                    Ok(val) => report.push(val),
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+
+            if self.eof() && report.is_empty() {
+                return None;
+            }
+            self.expect_literal(b"\r");
+            self.expect_literal(b"\n");
+            if !report.is_empty() {
+                return Some(Ok(report));
+            }
+        }
+    }
+}
+
+/// Reads reports one line at a time from any buffered reader, so only the
+/// current line is ever held in memory.
+pub struct ReportReader<R: BufRead> {
+    reader: R,
+    line: Vec<u8>,
+    line_number: usize,
+}
+
+impl<R: BufRead> ReportReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_number: 0,
+        }
+    }
+
+    /// The line the last report was read from, starting at 1.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl<R: BufRead> Iterator for ReportReader<R> {
+    type Item = Result<Vec<Level>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    self.line_number += 1;
+                    // A blank line holds no report, so move on to the next.
+                    let Some(report) = ReportData::new(&self.line[..]).read_report() else {
+                        continue;
+                    };
+                    return Some(report.map_err(|mut err| {
+                        if let Some(position) = &mut err.position {
+                            position.line += self.line_number - 1;
+                        }
+                        err
+                    }));
+                }
+                Err(err) => return Some(Err(ParseError::io(&err))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod day_two {
+    use super::{
+        check_report, first_violation, is_report_safe, is_report_safe_brute_force,
+        is_strictly_safe, DayTwo, Level, Monotonicity, ReportData, ReportReader, SafetyPolicy,
+        SafetyVerdict, UnsafeReason, VerdictSummary,
+    };
+    use aoc_common::{Answer, ParseErrorKind, Position, Solution};
+    use std::io::{self, BufReader, Read};
+
+    #[test]
+    fn report_data_peek() {
+        let data = setup_data();
+        let report_data = ReportData::new(data);
+        let peek = report_data.peek();
+        assert!(peek.is_some());
+        assert_eq!(b'7', *peek.unwrap());
+        assert_eq!(0, report_data.cursor);
+    }
+
+    #[test]
+    fn report_data_read_byte() {
+        let data = setup_data();
+        let mut report_data = ReportData::new(data);
+        let byte = report_data.read_byte();
+
+        assert!(byte.is_some());
+        assert_eq!(b'7', byte.unwrap());
+        assert_eq!(1, report_data.cursor);
+    }
+
+    #[test]
+    fn report_data_skip_space() {
+        let data = setup_data();
+        let mut report_data = ReportData::new(data);
+        report_data.cursor = 1;
+        report_data.skip_space();
+        assert_eq!(2, report_data.cursor);
+        assert_eq!(b'6', *report_data.peek().unwrap());
+
+        report_data.skip_space();
+        assert_eq!(2, report_data.cursor);
+        assert_eq!(b'6', *report_data.peek().unwrap());
+    }
+
+    #[test]
+    fn report_data_read_value() {
+        let data = setup_data();
+        let mut report_data = ReportData::new(data);
+
+        assert_eq!(Some(Ok(7)), report_data.read_value());
+        assert_eq!(Some(Ok(6)), report_data.read_value());
+        assert_eq!(Some(Ok(42)), report_data.read_value());
+        assert_eq!(Some(Ok(2)), report_data.read_value());
+        assert_eq!(Some(Ok(1)), report_data.read_value());
+        assert_eq!(None, report_data.read_value());
+    }
+
+    #[test]
+    fn report_data_read_report() {
+        let data = setup_data();
+        let mut report_data = ReportData::new(data);
+
+        assert_eq!(Some(Ok(vec![7, 6, 42, 2, 1,])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![1, 2, 7, 8, 9])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![9, 7, 6, 2, 1])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![1, 3, 2, 4, 5])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![8, 6, 4, 4, 1])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![1, 3, 6, 7, 9])), report_data.read_report());
+        assert!(report_data.read_report().is_none());
+        assert!(report_data.read_report().is_none());
+    }
+
+    #[test]
+    fn is_report_safe_example_one() {
+        let report = vec![7, 6, 4, 2, 1];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_example_two() {
+        let report = vec![1, 2, 7, 8, 9];
+        assert!(!is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_example_three() {
+        let report = vec![9, 7, 6, 2, 1];
+        assert!(!is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_example_four() {
+        let report = vec![1, 3, 2, 4, 5];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_example_five() {
+        let report = vec![8, 6, 4, 4, 1];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_example_six() {
+        let report = vec![1, 3, 6, 7, 9];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_decending_safe() {
+        let mut report = vec![7, 6, 4, 2, 1];
+        report.reverse();
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_accending_damper_safe() {
+        let report = vec![10, 6, 7, 5, 2];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_accending_unsafe() {
+        let report = vec![10, 6, 7, 2, 1];
+        assert!(!is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_inconistent_unsafe() {
+        let report = vec![10, 14, 15, 11];
+        assert!(!is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_no_difference_unsafe() {
+        let report = vec![10, 11, 11, 12];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_duplicates_first() {
+        let report = vec![14, 14, 11, 10, 7, 5, 2];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_bad_data() {
+        let report = vec![86, 87, 85, 87, 89, 92, 95];
+        assert!(!is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_bad_data_two() {
+        let report = vec![75, 78, 79, 82, 85, 85];
+        assert!(!is_strictly_safe(&report));
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_remove_first() {
+        let report = vec![9, 1, 2, 3, 4];
+        assert!(!is_strictly_safe(&report));
+        assert!(is_report_safe(&report).is_safe());
+
+        let report = vec![3, 2, 3, 4, 5];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_remove_second_direction() {
+        let report = vec![5, 6, 4, 3, 2];
+        assert!(is_report_safe(&report).is_safe());
+    }
+
+    #[test]
+    fn is_report_safe_short_reports() {
+        assert!(is_report_safe(&[]).is_safe());
+        assert!(is_report_safe(&[4]).is_safe());
+        assert!(is_report_safe(&[4, 40]).is_safe());
+        assert!(!is_strictly_safe(&[4, 40]));
+    }
+
+    #[test]
+    fn is_report_safe_verdicts() {
+        assert_eq!(SafetyVerdict::Safe, is_report_safe(&[7, 6, 4, 2, 1]));
+        assert_eq!(
+            SafetyVerdict::SafeWithRemoval { index: 1 },
+            is_report_safe(&[1, 3, 2, 4, 5])
+        );
+        assert_eq!(
+            SafetyVerdict::SafeWithRemoval { index: 2 },
+            is_report_safe(&[8, 6, 4, 4, 1])
+        );
+        assert_eq!(
+            SafetyVerdict::SafeWithRemoval { index: 0 },
+            is_report_safe(&[9, 1, 2, 3, 4])
+        );
+        assert_eq!(
+            SafetyVerdict::Unsafe {
+                first_violation_index: 2,
+                reason: UnsafeReason::Gap
+            },
+            is_report_safe(&[1, 2, 7, 8, 9])
+        );
+        assert_eq!(
+            SafetyVerdict::Unsafe {
+                first_violation_index: 2,
+                reason: UnsafeReason::DirectionChange
+            },
+            is_report_safe(&[86, 87, 85, 87, 89, 92, 95])
+        );
+        assert_eq!(
+            SafetyVerdict::Unsafe {
+                first_violation_index: 1,
+                reason: UnsafeReason::Zero
+            },
+            is_report_safe(&[5, 5, 5, 6])
+        );
+    }
+
+    #[test]
+    fn first_violation_reasons() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(None, first_violation(&[1, 3, 6, 7, 9], &policy));
+        assert_eq!(None, first_violation(&[], &policy));
+        assert_eq!(
+            Some((3, UnsafeReason::DirectionChange)),
+            first_violation(&[10, 8, 7, 8], &policy)
+        );
+        assert_eq!(
+            Some((1, UnsafeReason::Gap)),
+            first_violation(&[1, 5], &policy)
+        );
+    }
+
+    #[test]
+    fn verdict_summary() {
+        let data = setup_data();
+        let mut report_data = ReportData::new(data);
+        let mut summary = VerdictSummary::with_lines();
+        let mut counts = VerdictSummary::default();
+        let mut line = 0;
+        while let Some(report) = report_data.read_report() {
+            line += 1;
+            let verdict = is_report_safe(&report.unwrap());
+            summary.add(line, verdict);
+            counts.add(line, verdict);
+        }
+
+        assert_eq!(1, summary.safe);
+        assert_eq!(2, summary.safe_with_removal);
+        assert_eq!(3, summary.unsafe_count());
+        assert_eq!(
+            Some(&vec![2, 3]),
+            summary
+                .unsafe_lines
+                .as_ref()
+                .and_then(|lines| lines.get(&UnsafeReason::Gap))
+        );
+        assert!(summary
+            .to_string()
+            .ends_with("Unsafe (gap) on lines: 2, 3\nUnsafe (direction change) on lines: 1"));
+
+        assert_eq!(summary.unsafe_counts, counts.unsafe_counts);
+        assert_eq!(None, counts.unsafe_lines);
+        assert!(counts
+            .to_string()
+            .ends_with("unsafe: direction change           1\n"));
+    }
+
+    #[test]
+    fn check_report_policies() {
+        let plateaus = SafetyPolicy {
+            allow_plateaus: true,
+            allowed_faults: 0,
+            ..SafetyPolicy::default()
+        };
+        assert!(check_report(&[10, 11, 11, 12], &plateaus).is_safe());
+        assert!(check_report(&[11, 11, 10], &plateaus).is_safe());
+
+        let wide = SafetyPolicy {
+            max_step: 5,
+            allowed_faults: 0,
+            ..SafetyPolicy::default()
+        };
+        assert!(check_report(&[1, 6, 8], &wide).is_safe());
+        assert!(!check_report(&[1, 7, 8], &wide).is_safe());
+
+        let increasing = SafetyPolicy {
+            monotonicity: Monotonicity::Increasing,
+            ..SafetyPolicy::default()
+        };
+        assert!(check_report(&[1, 2, 3], &increasing).is_safe());
+        assert_eq!(
+            SafetyVerdict::Unsafe {
+                first_violation_index: 1,
+                reason: UnsafeReason::DirectionChange
+            },
+            check_report(&[3, 2, 1], &increasing)
+        );
+
+        let unlimited = SafetyPolicy {
+            allowed_faults: usize::MAX,
+            ..SafetyPolicy::default()
+        };
+        assert!(check_report(&[1, 9, 2, 20, 3, 3, 4], &unlimited).is_safe());
+        assert!(check_report(&[], &unlimited).is_safe());
+
+        let any = SafetyPolicy {
+            monotonicity: Monotonicity::Any,
+            allowed_faults: 0,
+            ..SafetyPolicy::default()
+        };
+        assert!(check_report(&[1, 3, 2, 4, 1], &any).is_safe());
+
+        let two_faults = SafetyPolicy {
+            allowed_faults: 2,
+            ..SafetyPolicy::default()
+        };
+        assert!(!is_report_safe(&[1, 9, 9, 2, 3]).is_safe());
+        assert_eq!(
+            SafetyVerdict::SafeWithRemoval { index: 1 },
+            check_report(&[1, 9, 9, 2, 3], &two_faults)
+        );
+    }
+
+    #[test]
+    fn monotonicity_from_str() {
+        assert_eq!(Ok(Monotonicity::Any), "any".parse());
+        assert_eq!(Ok(Monotonicity::Increasing), "increasing".parse());
+        assert!("sideways".parse::<Monotonicity>().is_err());
+    }
+
+    #[test]
+    fn check_report_matches_brute_force() {
+        let policies = [
+            SafetyPolicy::strict(),
+            SafetyPolicy::default(),
+            SafetyPolicy {
+                allowed_faults: 2,
+                ..SafetyPolicy::default()
+            },
+            SafetyPolicy {
+                allow_plateaus: true,
+                min_step: 2,
+                ..SafetyPolicy::default()
+            },
+            SafetyPolicy {
+                monotonicity: Monotonicity::Any,
+                max_step: 2,
+                ..SafetyPolicy::default()
+            },
+            SafetyPolicy {
+                monotonicity: Monotonicity::Decreasing,
+                allowed_faults: 2,
+                ..SafetyPolicy::default()
+            },
+        ];
+        for policy in &policies {
+            for len in 0..=6 {
+                let mut report = vec![1; len];
+                loop {
+                    assert_eq!(
+                        is_report_safe_brute_force(&report, policy),
+                        check_report(&report, policy).is_safe(),
+                        "{report:?} {policy:?}"
+                    );
+                    let Some(idx) = report.iter().rposition(|val| *val < 6) else {
+                        break;
+                    };
+                    report[idx] += 1;
+                    report[idx + 1..].fill(1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solution() {
+        let reports = DayTwo::parse(&setup_data()).unwrap();
+        assert_eq!(6, reports.len());
+        assert_eq!(Answer::from(1), DayTwo::part_one(&reports));
+        assert_eq!(Answer::from(3), DayTwo::part_two(&reports));
+    }
+
+    #[test]
+    fn report_data_read_value_signs_and_whitespace() {
+        let mut report_data = ReportData::new(b"-3 +4\t\t12  \r\n5\t6\r\n\r\n".to_vec());
+        assert_eq!(Some(Ok(vec![-3, 4, 12])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![5, 6])), report_data.read_report());
+        assert_eq!(None, report_data.read_report());
+
+        let mut report_data = ReportData::new(b"\n  \n1 2\r\n\r\n\n3\n\n".to_vec());
+        assert_eq!(Some(Ok(vec![1, 2])), report_data.read_report());
+        assert_eq!(Some(Ok(vec![3])), report_data.read_report());
+        assert_eq!(None, report_data.read_report());
+
+        let mut report_data = ReportData::new(b"7 6\r".to_vec());
+        assert_eq!(Some(Ok(vec![7, 6])), report_data.read_report());
+        assert_eq!(None, report_data.read_report());
+    }
+
+    #[test]
+    fn report_data_read_value_errors() {
+        for (data, token, column) in [
+            ("1 2x 3", "2x", 3),
+            ("1 - 3", "-", 3),
+            ("1 -+3", "-+3", 3),
+            ("abc", "abc", 1),
+            ("1 2\r3", "2\r3", 3),
+            ("5 99999999999999999999", "99999999999999999999", 3),
+            ("4 2x\r\n", "2x", 3),
+        ] {
+            let mut report_data = ReportData::new(data.as_bytes());
+            let err = report_data.read_report().unwrap().unwrap_err();
+            assert_eq!(
+                ParseErrorKind::MalformedNumber(String::from(token)),
+                err.kind,
+                "{data:?}"
+            );
+            assert_eq!(Some(Position::new(1, column)), err.position, "{data:?}");
+        }
+    }
+
+    #[test]
+    fn report_reader_error_position() {
+        let mut reader = ReportReader::new(&b"1 2\n3 4\n5 x\n"[..]);
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_ok());
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(Some(Position::new(3, 3)), err.position);
+        assert!(DayTwo::parse(b"1 2\n3 4\n5 x\n").is_err());
+    }
+
+    #[test]
+    fn report_reader() {
+        let reports: Vec<Vec<Level>> = ReportReader::new(&setup_data()[..])
+            .collect::<aoc_common::Result<_>>()
+            .unwrap();
+        assert_eq!(6, reports.len());
+        assert_eq!(vec![7, 6, 42, 2, 1], reports[0]);
+        assert_eq!(vec![1, 3, 6, 7, 9], reports[5]);
+
+        let mut reader = ReportReader::new(&b"1 2\n\n3 4\n"[..]);
+        assert_eq!(Some(vec![1, 2]), reader.next().transpose().unwrap());
+        assert_eq!(Some(vec![3, 4]), reader.next().transpose().unwrap());
+        assert_eq!(3, reader.line_number());
+        assert!(reader.next().is_none());
+
+        let mut reader = ReportReader::new(&b"\r\n1 x\n"[..]);
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(Some(Position::new(2, 3)), err.position);
+
+        let reports = DayTwo::parse(b"7 6 4 2 1\n\n1 2 7 8 9\n\n").unwrap();
+        assert_eq!(2, reports.len());
+        assert_eq!(Answer::from(1), DayTwo::part_one(&reports));
+    }
+
+    /// Generates reports on the fly so the whole input never exists at once.
+    struct Generated {
+        remaining: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Generated {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                if self.remaining == 0 {
+                    return Ok(0);
+                }
+                self.remaining -= 1;
+                self.pending = format!("{} 2 3 4 5\n", 1 + self.remaining % 2).into_bytes();
+            }
+            let len = buf.len().min(self.pending.len());
+            buf[..len].copy_from_slice(&self.pending[..len]);
+            self.pending.drain(..len);
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn report_reader_streams() {
+        let reader = BufReader::with_capacity(
+            16,
+            Generated {
+                remaining: 100_000,
+                pending: Vec::new(),
+            },
+        );
+        let safe = ReportReader::new(reader)
+            .map(|report| is_strictly_safe(&report.unwrap()))
+            .filter(|safe| *safe)
+            .count();
+        assert_eq!(50_000, safe);
+    }
+
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+
+    #[test]
+    fn report_reader_io_error() {
+        let err = ReportReader::new(BufReader::new(Failing))
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(ParseErrorKind::Io(String::from("disk on fire")), err.kind);
+    }
+
+    fn setup_data() -> Vec<u8> {
+        let data = br"7 6 42 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        Vec::from(data)
+    }
+}
@@ -1,348 +1,153 @@
-use std::fs;
-use std::path::Path;
-
-fn main() {
-    let data = read_file(String::from("./day-two/input/reports.txt"));
-    let mut data = ReportData::new(data);
-
-    let mut safe_reports: Vec<Vec<usize>> = Vec::new();
-    let mut unsafe_reports: Vec<Vec<usize>> = Vec::new();
-
-    while let Some(report) = data.read_report() {
-        if is_report_safe(&report) {
-            safe_reports.push(report);
-        } else {
-            unsafe_reports.push(report);
-        }
-    }
-
-    println!(
-        "There are {} safe reports and {} unsafe reports.",
-        safe_reports.len(),
-        unsafe_reports.len()
-    );
+use aoc_common::cli::{self, Args};
+use aoc_common::{log, open_input, Result, Solution};
+use day_two::{check_report, DayTwo, ReportReader, SafetyPolicy, VerdictSummary};
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "Usage: day-two [--input <PATH>] [--explain] [--min-step <N>] [--max-step <N>]
+               [--faults <N>] [--plateaus] [--monotonicity <increasing|decreasing|either|any>]
+
+Reports are read a line at a time from the day's input file, or from PATH
+when given, with `-` meaning stdin.
+
+The defaults are the puzzle's rules: steps of 1 to 3, one fault removed by the
+Problem Dampener, no plateaus and either direction.";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    input: Option<String>,
+    explain: bool,
+    policy: SafetyPolicy,
 }
 
-#[derive(Debug)]
-struct DamperedBool {
-    val: bool,
-    pub damper: bool,
+fn main() -> ExitCode {
+    log::init_from_env();
+    cli::run_main(USAGE, parse_args, run)
 }
 
-impl DamperedBool {
-    pub fn new() -> Self {
-        DamperedBool {
-            val: true,
-            damper: true,
+/// Returns `None` when help was asked for.
+fn parse_args<I: Iterator<Item = String>>(
+    mut args: Args<I>,
+) -> std::result::Result<Option<Options>, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => options.input = Some(args.value(&arg)?),
+            "--explain" => options.explain = true,
+            "--min-step" => options.policy.min_step = parse_number(&args.value(&arg)?)?,
+            "--max-step" => options.policy.max_step = parse_number(&args.value(&arg)?)?,
+            "--faults" => options.policy.allowed_faults = parse_number(&args.value(&arg)?)?,
+            "--plateaus" => options.policy.allow_plateaus = true,
+            "--monotonicity" => options.policy.monotonicity = args.value(&arg)?.parse()?,
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument `{arg}`")),
         }
     }
 
-    pub fn set_false(&mut self) -> bool {
-        if self.damper {
-            self.damper = false;
-            false
-        } else {
-            self.val = false;
-            true
-        }
-    }
-
-    pub fn get_val(&self) -> bool {
-        self.val
+    if options.policy.min_step > options.policy.max_step {
+        return Err(String::from("`--min-step` must not exceed `--max-step`"));
     }
+    Ok(Some(options))
 }
 
-fn is_report_safe(report: &Vec<usize>) -> bool {
-    let log_str = format!("is_report_safe({:?})", &report);
-    let mut report = report.iter();
-    let mut safe = DamperedBool::new();
-    let mut is_accending: Option<bool> = None;
-    let mut last_val = *report.next().unwrap();
-
-    let mut trace_log_str = String::from("");
-
-    for val in report {
-        let diff: isize = (*val as isize) - (last_val as isize);
-        if !(-4 < diff && diff < 4 && diff != 0) {
-            if !safe.set_false() {
-                continue;
-            }
-        } else if let Some(accending) = is_accending {
-            if accending && 0 > diff {
-                if !safe.set_false() {
-                    continue;
-                }
-            } else if !accending && 0 < diff && !safe.set_false() {
-                continue;
-            }
-        } else {
-            is_accending = Some(*val > last_val);
-        }
-
-        trace_log_str = format!("{trace_log_str}\nLast Val: {last_val} | Val: {val} | Accending: {is_accending:?} | Diff: {diff} | Safe: {safe:?}");
-        last_val = *val;
-
-        if !safe.get_val() {
-            break;
-        }
-    }
-    println!("{trace_log_str}");
-    println!("{log_str} -> {safe:?}");
-    safe.get_val()
-}
-
-struct ReportData {
-    data: Box<[u8]>,
-    pub cursor: usize,
-    data_len: usize,
+fn parse_number<T: FromStr>(value: &str) -> std::result::Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a whole number"))
 }
 
-impl ReportData {
-    pub fn new(data: Vec<u8>) -> Self {
-        Self {
-            data: data.as_slice().into(),
-            cursor: 0,
-            data_len: data.len(),
-        }
-    }
-
-    #[inline]
-    fn eof(&self) -> bool {
-        self.cursor > self.data_len - 1
-    }
-
-    fn peek(&self) -> Option<&u8> {
-        if self.eof() {
-            None
-        } else {
-            Some(&self.data[self.cursor])
-        }
-    }
-
-    fn read_byte(&mut self) -> Option<u8> {
-        if self.eof() {
-            None
-        } else {
-            let byte = Some(self.data[self.cursor]);
-            self.cursor += 1;
-            byte
-        }
-    }
-
-    fn skip_space(&mut self) {
-        while let Some(b) = self.peek() {
-            if *b == b' ' {
-                let _ = self.read_byte();
-            } else {
-                break;
-            }
-        }
-    }
-
-    fn read_value(&mut self) -> Option<usize> {
-        let mut buff: Vec<u8> = Vec::new();
-        while let Some(byte) = self.peek() {
-            if *byte == b' ' {
-                self.skip_space();
-                break;
-            }
-            if *byte == b'\n' {
-                break;
-            }
-            buff.push(self.read_byte().unwrap());
+fn run(options: &Options) -> Result<()> {
+    let input: Box<dyn BufRead> = match options.input.as_deref() {
+        Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(open_input(path)?),
+        None => Box::new(open_input(DayTwo::INPUT)?),
+    };
+    let strict = SafetyPolicy {
+        allowed_faults: 0,
+        ..options.policy
+    };
+
+    let mut strictly_safe_reports = 0;
+    let mut summary = if options.explain {
+        VerdictSummary::with_lines()
+    } else {
+        VerdictSummary::default()
+    };
+    let mut reports = ReportReader::new(input);
+
+    while let Some(report) = reports.next() {
+        let report = report?;
+        let line = reports.line_number();
+        if check_report(&report, &strict).is_safe() {
+            strictly_safe_reports += 1;
         }
-
-        buff.reverse();
-        buff.into_iter()
-            .enumerate()
-            .map(|(idx, val)| (val - 48) as usize * (10_usize.pow((idx) as u32)))
-            .reduce(|acc, val| acc + val)
+        summary.add(line, check_report(&report, &options.policy));
     }
 
-    fn read_report(&mut self) -> Option<Vec<usize>> {
-        let mut report: Vec<usize> = Vec::new();
-
-        while let Some(val) = self.read_value() {
-            report.push(val);
-        }
-
-        if self.peek().is_some() {
-            let _ = self.read_byte();
-            Some(report)
-        } else if report.is_empty() {
-            None
-        } else {
-            Some(report)
-        }
+    println!("There are {strictly_safe_reports} safe reports without the Problem Dampener.");
+    println!(
+        "There are {} safe reports and {} unsafe reports.",
+        summary.safe + summary.safe_with_removal,
+        summary.unsafe_count()
+    );
+    if options.explain {
+        println!("\n{summary}");
     }
-}
-
-fn read_file(file_path: String) -> Vec<u8> {
-    let file_path = Path::new(&file_path);
-    let data: Vec<u8> = fs::read(file_path).unwrap();
-    data
+    Ok(())
 }
 
 #[cfg(test)]
-mod day_two {
-    use super::{is_report_safe, ReportData};
-
-    #[test]
-    fn report_data_peek() {
-        let data = setup_data();
-        let report_data = ReportData::new(data);
-        let peek = report_data.peek();
-        assert!(peek.is_some());
-        assert_eq!(b'7', *peek.unwrap());
-        assert_eq!(0, report_data.cursor);
-    }
-
-    #[test]
-    fn report_data_read_byte() {
-        let data = setup_data();
-        let mut report_data = ReportData::new(data);
-        let byte = report_data.read_byte();
-
-        assert!(byte.is_some());
-        assert_eq!(b'7', byte.unwrap());
-        assert_eq!(1, report_data.cursor);
-    }
-
-    #[test]
-    fn report_data_skip_space() {
-        let data = setup_data();
-        let mut report_data = ReportData::new(data);
-        report_data.cursor = 1;
-        report_data.skip_space();
-        assert_eq!(2, report_data.cursor);
-        assert_eq!(b'6', *report_data.peek().unwrap());
-
-        report_data.skip_space();
-        assert_eq!(2, report_data.cursor);
-        assert_eq!(b'6', *report_data.peek().unwrap());
-    }
-
-    #[test]
-    fn report_data_read_value() {
-        let data = setup_data();
-        let mut report_data = ReportData::new(data);
-
-        assert_eq!(Some(7), report_data.read_value());
-        assert_eq!(Some(6), report_data.read_value());
-        assert_eq!(Some(42), report_data.read_value());
-        assert_eq!(Some(2), report_data.read_value());
-        assert_eq!(Some(1), report_data.read_value());
-        assert_eq!(None, report_data.read_value());
-    }
-
-    #[test]
-    fn report_data_read_report() {
-        let data = setup_data();
-        let mut report_data = ReportData::new(data);
-
-        assert_eq!(Some(vec![7, 6, 42, 2, 1,]), report_data.read_report());
-        assert_eq!(Some(vec![1, 2, 7, 8, 9]), report_data.read_report());
-        assert_eq!(Some(vec![9, 7, 6, 2, 1]), report_data.read_report());
-        assert_eq!(Some(vec![1, 3, 2, 4, 5]), report_data.read_report());
-        assert_eq!(Some(vec![8, 6, 4, 4, 1]), report_data.read_report());
-        assert_eq!(Some(vec![1, 3, 6, 7, 9]), report_data.read_report());
-        assert!(report_data.read_report().is_none());
-        assert!(report_data.read_report().is_none());
-    }
-
-    #[test]
-    fn is_report_safe_example_one() {
-        let report = vec![7, 6, 4, 2, 1];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_example_two() {
-        let report = vec![1, 2, 7, 8, 9];
-        assert!(!is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_example_three() {
-        let report = vec![9, 7, 6, 2, 1];
-        assert!(!is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_example_four() {
-        let report = vec![1, 3, 2, 4, 5];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_example_five() {
-        let report = vec![8, 6, 4, 4, 1];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_example_six() {
-        let report = vec![1, 3, 6, 7, 9];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_decending_safe() {
-        let mut report = vec![7, 6, 4, 2, 1];
-        report.reverse();
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_accending_damper_safe() {
-        let report = vec![10, 6, 7, 5, 2];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_accending_unsafe() {
-        let report = vec![10, 6, 7, 2, 1];
-        assert!(!is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_inconistent_unsafe() {
-        let report = vec![10, 14, 15, 11];
-        assert!(!is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_no_difference_unsafe() {
-        let report = vec![10, 11, 11, 12];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_duplicates_first() {
-        let report = vec![14, 14, 11, 10, 7, 5, 2];
-        assert!(is_report_safe(&report));
-    }
-
-    #[test]
-    fn is_report_safe_bad_data() {
-        let report = vec![86, 87, 85, 87, 89, 92, 95];
-        assert!(!is_report_safe(&report));
-    }
-
-    #[test]
-    #[ignore = "This day is not finished, I'll come back to it later"]
-    fn is_report_safe_bad_data_two() {
-        let report = vec![75, 78, 79, 82, 85, 85];
-        assert!(!is_report_safe(&report));
-    }
-
-    fn setup_data() -> Vec<u8> {
-        let data = br"7 6 42 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        Vec::from(data)
+mod options {
+    use super::{parse_args, Options};
+    use aoc_common::cli::Args;
+    use day_two::{Monotonicity, SafetyPolicy};
+
+    #[test]
+    fn parse_args_defaults() {
+        assert_eq!(
+            Ok(Some(Options::default())),
+            parse_args(Args::from_strs(&[]))
+        );
+        assert_eq!(Ok(None), parse_args(Args::from_strs(&["--help"])));
+    }
+
+    #[test]
+    fn parse_args_policy() {
+        let options = parse_args(Args::from_strs(&[
+            "--input",
+            "-",
+            "--explain",
+            "--max-step",
+            "5",
+            "--faults",
+            "2",
+            "--plateaus",
+            "--monotonicity",
+            "increasing",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert!(options.explain);
+        assert_eq!(Some("-"), options.input.as_deref());
+        assert_eq!(
+            SafetyPolicy {
+                min_step: 1,
+                max_step: 5,
+                allowed_faults: 2,
+                allow_plateaus: true,
+                monotonicity: Monotonicity::Increasing,
+            },
+            options.policy
+        );
+    }
+
+    #[test]
+    fn parse_args_errors() {
+        assert!(parse_args(Args::from_strs(&["--faults"])).is_err());
+        assert!(parse_args(Args::from_strs(&["--faults", "x"])).is_err());
+        assert!(parse_args(Args::from_strs(&["--monotonicity", "up"])).is_err());
+        assert!(parse_args(Args::from_strs(&["--min-step", "4"])).is_err());
+        assert!(parse_args(Args::from_strs(&["--bogus"])).is_err());
     }
 }